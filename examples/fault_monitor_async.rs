@@ -0,0 +1,132 @@
+#![no_std]
+#![no_main]
+
+//! Periodic DRV8301 health reporting from a dedicated embassy task
+//!
+//! `fault_monitor` owns the driver, polls the status registers every
+//! [`POLL_INTERVAL`] and sends a [`FaultDiff`] to the main task through a
+//! channel whenever the fault status changes.
+
+use defmt::{Debug2Format, info, warn};
+use drv8301_dd::prelude::*;
+use drv8301_dd::{DrvInterface, FaultDiff};
+use embassy_embedded_hal::shared_bus::SpiDeviceError;
+use embassy_embedded_hal::shared_bus::asynch::spi::SpiDevice;
+use embassy_executor::Spawner;
+use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+use embassy_sync::channel::{Channel, Sender};
+use embassy_sync::mutex::Mutex;
+use embassy_time::{Duration, Ticker};
+use esp_hal::{
+    dma::{DmaRxBuf, DmaTxBuf},
+    dma_buffers,
+    gpio::{Level, Output, OutputConfig},
+    interrupt::software::SoftwareInterruptControl,
+    spi::{
+        Mode,
+        master::{Config as SpiConfig, Spi, SpiDmaBus},
+    },
+    time::Rate,
+    timer::timg::TimerGroup,
+};
+use panic_rtt_target as _;
+use rtt_target::rtt_init_defmt;
+use static_cell::StaticCell;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+/// How often the monitor task polls the status registers
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+type SpiMutex = Mutex<NoopRawMutex, SpiDmaBus<'static, esp_hal::Async>>;
+type DrvSpiDevice =
+    SpiDevice<'static, NoopRawMutex, SpiDmaBus<'static, esp_hal::Async>, Output<'static>>;
+type DrvSpiError = SpiDeviceError<esp_hal::spi::Error, core::convert::Infallible>;
+type Drv = Drv8301Async<DrvInterface<DrvSpiDevice>, DrvSpiError>;
+type FaultChannel = Channel<NoopRawMutex, FaultDiff, 4>;
+
+static SPI_BUS: StaticCell<SpiMutex> = StaticCell::new();
+static FAULTS: StaticCell<FaultChannel> = StaticCell::new();
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) {
+    rtt_init_defmt!();
+    info!("Init!");
+
+    let p = esp_hal::init(esp_hal::Config::default());
+
+    let timg0 = TimerGroup::new(p.TIMG0);
+    let sw_ints = SoftwareInterruptControl::new(p.SW_INTERRUPT);
+    esp_rtos::start(timg0.timer0, sw_ints.software_interrupt0);
+
+    // Configure SPI pins
+    let sclk = p.GPIO6;
+    let miso = p.GPIO5;
+    let mosi = p.GPIO7;
+    let cs = p.GPIO4;
+
+    // Create CS pin as output
+    let cs_pin = Output::new(cs, Level::High, OutputConfig::default());
+
+    // Configure DMA buffers
+    let (rx_buffer, rx_descriptors, tx_buffer, tx_descriptors) = dma_buffers!(256);
+    let dma_rx_buf = DmaRxBuf::new(rx_descriptors, rx_buffer).unwrap();
+    let dma_tx_buf = DmaTxBuf::new(tx_descriptors, tx_buffer).unwrap();
+
+    // Configure SPI - DRV8301: CPOL=0, CPHA=1 (Mode 1), max 10MHz
+    let spi = Spi::new(
+        p.SPI2,
+        SpiConfig::default()
+            .with_frequency(Rate::from_mhz(1))
+            .with_mode(Mode::_1),
+    )
+    .unwrap()
+    .with_sck(sclk)
+    .with_miso(miso)
+    .with_mosi(mosi)
+    .with_dma(p.DMA_CH0)
+    .with_buffers(dma_rx_buf, dma_tx_buf)
+    .into_async();
+
+    // Create shared bus and wrap with SpiDevice
+    let spi_bus = SPI_BUS.init(Mutex::new(spi));
+    let spi_device = SpiDevice::new(spi_bus, cs_pin);
+
+    let faults = FAULTS.init(Channel::new());
+    spawner.must_spawn(fault_monitor(
+        Drv8301Async::new(spi_device),
+        faults.sender(),
+    ));
+
+    loop {
+        let diff = faults.receive().await;
+        if diff.set.has_overcurrent() {
+            warn!("Overcurrent raised: {}", diff.set);
+        }
+        info!("Faults set: {}, cleared: {}", diff.set, diff.cleared);
+    }
+}
+
+/// Poll the fault status and report every change to the main task
+#[embassy_executor::task]
+async fn fault_monitor(mut drv: Drv, faults: Sender<'static, NoopRawMutex, FaultDiff, 4>) {
+    let mut last = FaultStatus::default();
+    let mut ticker = Ticker::every(POLL_INTERVAL);
+
+    loop {
+        ticker.next().await;
+
+        let status = match drv.get_fault_status().await {
+            Ok(status) => status,
+            Err(e) => {
+                warn!("Fault status read failed: {}", Debug2Format(&e));
+                continue;
+            }
+        };
+
+        if status != last {
+            faults.send(status.diff(&last)).await;
+            last = status;
+        }
+    }
+}
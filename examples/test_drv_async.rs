@@ -1,8 +1,8 @@
 #![no_std]
 #![no_main]
 
-use defmt::info;
-use drv8301_dd::{Drv8301Async, DrvError, FaultStatus, OcAdjSet, OcpMode, ShuntAmplifierGain};
+use defmt::{Debug2Format, error, info};
+use drv8301_dd::prelude::*;
 use embassy_embedded_hal::shared_bus::asynch::spi::SpiDevice;
 use embassy_executor::Spawner;
 use embassy_sync::blocking_mutex::raw::NoopRawMutex;
@@ -73,7 +73,10 @@ async fn main(_spawner: Spawner) {
     let spi_bus = SPI_BUS.init(Mutex::new(spi));
     let spi_device = SpiDevice::new(spi_bus, cs_pin);
 
-    init_drv(spi_device).await.unwrap();
+    // Report driver failures instead of panicking so the cause shows up in the log
+    if let Err(e) = init_drv(spi_device).await {
+        error!("DRV8301 init failed: {}", Debug2Format(&e));
+    }
 
     loop {
         info!("Hello world!");
@@ -81,6 +84,7 @@ async fn main(_spawner: Spawner) {
     }
 }
 
+/// Configure the DRV8301, propagating any driver error to the caller
 async fn init_drv<SPI, E>(spi: SPI) -> Result<(), DrvError<E>>
 where
     SPI: embedded_hal_async::spi::SpiDevice<Error = E>,
@@ -1,8 +1,8 @@
 #![no_std]
 #![no_main]
 
-use defmt::info;
-use drv8301_dd::{Drv8301, DrvError, FaultStatus, OcAdjSet, OcpMode, ShuntAmplifierGain};
+use defmt::{Debug2Format, error, info};
+use drv8301_dd::prelude::*;
 use embedded_hal_bus::spi::ExclusiveDevice;
 use esp_hal::{
     delay::Delay,
@@ -60,7 +60,10 @@ fn main() -> ! {
     // Wrap SpiBus with ExclusiveDevice to get SpiDevice
     let spi_device = ExclusiveDevice::new(spi, cs_pin, delay).unwrap();
 
-    init_drv(spi_device).unwrap();
+    // Report driver failures instead of panicking so the cause shows up in the log
+    if let Err(e) = init_drv(spi_device) {
+        error!("DRV8301 init failed: {}", Debug2Format(&e));
+    }
 
     let delay = Delay::new();
     loop {
@@ -69,6 +72,7 @@ fn main() -> ! {
     }
 }
 
+/// Configure the DRV8301, propagating any driver error to the caller
 fn init_drv<SPI, E>(spi: SPI) -> Result<(), DrvError<E>>
 where
     SPI: embedded_hal::spi::SpiDevice<Error = E>,
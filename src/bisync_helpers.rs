@@ -52,6 +52,8 @@ where
     op.write_async(f).await
 }
 
+// Read-modify-write. A failed read (SPI or frame error) is returned before anything is
+// written, so a register is never rewritten from a partially-known value.
 #[allow(dead_code)]
 #[only_sync]
 fn modify_internal<'a, Interface, Register, Access, R>(
@@ -0,0 +1,41 @@
+//! Current shunt amplifier (CSA) conversions
+//!
+//! The DRV8301 shunt amplifiers output `Vout = (Vref / 2) - Gain × (SN - SP)`. With current
+//! flowing through the shunt from SP to SN (positive `current_a`), the output rises above `Vref / 2`.
+
+use crate::ShuntAmplifierGain;
+
+/// Expected CSA output voltage for a given phase current
+///
+/// * `current_a` - Current through the shunt in amps (positive from SP to SN)
+/// * `vref_v` - Voltage on the REF pin
+/// * `shunt_ohm` - Shunt resistance in ohms
+/// * `gain` - Configured amplifier gain
+pub fn expected_csa_voltage(
+    current_a: f32,
+    vref_v: f32,
+    shunt_ohm: f32,
+    gain: ShuntAmplifierGain,
+) -> f32 {
+    vref_v / 2.0 + gain.volts_per_volt() as f32 * current_a * shunt_ohm
+}
+
+/// Phase current implied by a CSA output voltage
+///
+/// This is the inverse of [`expected_csa_voltage`]; the arguments have the same meaning.
+pub fn phase_current_a(
+    csa_voltage_v: f32,
+    vref_v: f32,
+    shunt_ohm: f32,
+    gain: ShuntAmplifierGain,
+) -> f32 {
+    (csa_voltage_v - vref_v / 2.0) / (gain.volts_per_volt() as f32 * shunt_ohm)
+}
+
+/// Check whether a CSA output sample is too close to a rail to be trusted
+///
+/// The amplifier output swings between ground and `vref_v`; a sample within `headroom_v`
+/// of either rail may be clipped and should be rejected by the control loop.
+pub fn is_saturated(csa_voltage_v: f32, vref_v: f32, headroom_v: f32) -> bool {
+    csa_voltage_v <= headroom_v || csa_voltage_v >= vref_v - headroom_v
+}
@@ -0,0 +1,55 @@
+//! Dead-time sizing for the DTC resistor
+//!
+//! Dead time is set in hardware by the resistor on the DTC pin (roughly 50 ns to 500 ns),
+//! but how much is needed depends on the gate drive current configured over SPI: a FET
+//! with gate charge `Qg` takes about `Qg / I` to switch when driven with current `I`.
+
+use crate::GateCurrent;
+
+/// Shortest dead time the DRV8301 inserts, with the DTC pin tied to ground
+pub const MIN_DEADTIME_NS: f32 = 50.0;
+
+/// Margin applied to the ideal `Qg / I` switching time, since the peak drive current is not
+/// sustained across the whole gate charge
+const SAFETY_FACTOR: f32 = 2.0;
+
+/// Conservative dead-time estimate in nanoseconds for a gate current setting and FET gate charge
+///
+/// * `gate_current` - Configured peak gate drive current; the reserved setting is treated as
+///   the lowest (0.25 A)
+/// * `gate_charge_nc` - Total gate charge `Qg` of the FET in nanocoulombs, from its datasheet
+///
+/// Returns twice the ideal switching time `Qg / I`, and never less than [`MIN_DEADTIME_NS`].
+pub const fn min_deadtime_ns(gate_current: GateCurrent, gate_charge_nc: f32) -> f32 {
+    let peak_ma = match gate_current.peak_ma() {
+        Some(ma) => ma,
+        None => 250,
+    };
+    // nC / A = ns
+    let switching_ns = gate_charge_nc / (peak_ma as f32 / 1000.0);
+    (switching_ns * SAFETY_FACTOR).max(MIN_DEADTIME_NS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.5,
+            "{actual} ns, expected about {expected} ns"
+        );
+    }
+
+    #[test]
+    fn min_deadtime_scales_with_gate_current() {
+        // Small FET at full drive current: 17.6 ns ideal switching, clamped to the minimum
+        assert_eq!(min_deadtime_ns(GateCurrent::High, 30.0), MIN_DEADTIME_NS);
+        // Same FET at 0.7 A: 42.9 ns ideal, 85.7 ns with margin
+        assert_close(min_deadtime_ns(GateCurrent::Medium, 30.0), 85.7);
+        // 50 nC FET at 0.25 A: 200 ns ideal, 400 ns with margin
+        let low = min_deadtime_ns(GateCurrent::Low, 50.0);
+        assert_close(low, 400.0);
+        assert_eq!(min_deadtime_ns(GateCurrent::Reserved, 50.0), low);
+    }
+}
@@ -1,6 +1,10 @@
-use super::{RegisterInterface, SpiDevice, bisync, only_async, only_sync};
-use crate::{DrvError, DrvInterface, DrvLowLevel, FaultStatus};
-use crate::{GateCurrent, OcAdjSet, OcpMode, OctwMode, ShuntAmplifierGain};
+use super::{DelayNs, RegisterInterface, SpiDevice, bisync, only_async, only_sync};
+use crate::{
+    CsaChannel, DeviceState, DeviceVariant, Diagnosis, DrvConfig, DrvError, DrvInterface,
+    DrvLowLevel, Fault, FaultOutcome, FaultStatus, FieldId, FrameLog, OctwEvent, OvercurrentConfig,
+    ProtocolConfig, SpiTiming, TimingAdvice, debug_assert_fits,
+};
+use crate::{GateCurrent, OcAdjSet, OcpMode, OctwMode, PwmMode, RegisterDump, ShuntAmplifierGain};
 
 #[bisync]
 impl<SpiBus, E> RegisterInterface for DrvInterface<SpiBus>
@@ -17,36 +21,81 @@ where
         _size_bits: u32,
         data: &mut [u8],
     ) -> Result<(), Self::Error> {
-        // Build read command: bit 15 = 1 (read), bits 14:11 = address, bits 10:0 = don't care
-        let cmd: u16 = 0x8000 | ((address as u16 & 0x0F) << 11);
-        let cmd_bytes = cmd.to_be_bytes();
+        if self.strict_buffers {
+            check_buffer_len(data.len())?;
+        }
 
-        // First transaction: send read command
-        let mut response_bytes = [0u8; 2];
-        self.spi_bus
-            .transfer(&mut response_bytes, &cmd_bytes)
-            .await
-            .map_err(DrvError::Spi)?;
+        #[cfg(feature = "transaction-timing")]
+        let start = self.clock.map(|now| now());
 
-        // Second transaction: send same command to get actual data (N+1 timing)
-        let mut read_response = [0u8; 2];
-        self.spi_bus
-            .transfer(&mut read_response, &cmd_bytes)
-            .await
-            .map_err(DrvError::Spi)?;
+        let spi_bus = self
+            .spi_bus
+            .as_mut()
+            .ok_or(DrvError::NotSupported("SPI bus detached"))?;
 
-        let response = u16::from_be_bytes(read_response);
+        // Transient bus errors are retried; writes never are. An all-ones response (MISO
+        // pulled high, e.g. floating nSCS) is re-read until `link_down_frames` in a row have
+        // been seen, then reported as a dead link. Other frame errors fail immediately.
+        let mut retries = self.spi_retries;
+        let mut all_ones = 0u8;
+        let response = loop {
+            match read_response(spi_bus, &mut self.frames, self.protocol, address).await {
+                Err(DrvError::Spi(_)) if retries > 0 => retries -= 1,
+                Ok(0xFFFF) if self.link_down_frames > 0 => {
+                    all_ones += 1;
+                    if all_ones >= self.link_down_frames {
+                        return Err(DrvError::LinkDown);
+                    }
+                }
+                result => break result?,
+            }
+        };
 
-        // Check for frame error (bit 15 = 1 in response)
-        if (response & 0x8000) != 0 {
+        // Check for frame error (bit 15 = 1 in response for the DRV8301 layout)
+        if (response & self.protocol.frame_error_bit()) != 0 {
+            // Development aid only: never active in release builds
+            #[cfg(all(feature = "panic-on-frame-error", debug_assertions))]
+            panic!(
+                "DRV8301 frame error: command {:#x}, response {:#x}",
+                self.protocol.read_command(address),
+                response
+            );
+            #[allow(unreachable_code)]
             return Err(DrvError::FrameError);
         }
 
-        // Extract 11-bit data and store in output buffer (big-endian)
+        // Software integrity check: the frame carries no CRC, so a second read must agree on
+        // every bit the first read can't have cleared. The first response is the one kept.
+        if self.double_read {
+            let again = read_response(spi_bus, &mut self.frames, self.protocol, address).await?;
+            let mask = double_read_mask(address);
+            if again & mask != response & mask {
+                return Err(DrvError::FrameError);
+            }
+        }
         let reg_data = response & 0x07FF;
-        if data.len() >= 2 {
-            data[0] = (reg_data >> 8) as u8;
-            data[1] = reg_data as u8;
+
+        // Store the 11-bit data in the output buffer (big-endian); a short buffer in
+        // lenient mode is left untouched
+        if let Some(out) = data.get_mut(..FRAME_BYTES) {
+            out.copy_from_slice(&reg_data.to_be_bytes());
+        }
+
+        #[cfg(feature = "transaction-timing")]
+        if let Some(elapsed) = elapsed_us(self.clock, start) {
+            self.last_transaction_us = Some(elapsed);
+        }
+
+        #[cfg(all(feature = "startup-dump", any(feature = "defmt", feature = "log")))]
+        if !self.startup_dumped {
+            self.startup_dumped = true;
+            log_startup_dump(
+                spi_bus,
+                &mut self.frames,
+                self.protocol,
+                (address == 0x00).then_some(reg_data),
+            )
+            .await;
         }
 
         Ok(())
@@ -58,28 +107,303 @@ where
         _size_bits: u32,
         data: &[u8],
     ) -> Result<(), Self::Error> {
-        // Extract 11-bit data from buffer (big-endian)
-        let reg_data = if data.len() >= 2 {
-            ((data[0] as u16) << 8) | (data[1] as u16)
-        } else if data.len() == 1 {
-            data[0] as u16
-        } else {
-            0
-        };
+        if self.strict_buffers {
+            check_buffer_len(data.len())?;
+        }
+
+        // Extract 11-bit data from buffer (big-endian), forcing reserved bits to 0. In lenient
+        // mode missing bytes read as 0 and extra bytes are ignored.
+        let byte = |i: usize| data.get(i).copied().unwrap_or(0);
+        let reg_data = u16::from_be_bytes([byte(0), byte(1)]) & write_mask(address);
+
+        // Every control register 1 write passes through here, so the OC ceiling holds for
+        // set_oc_threshold, whole-config writes and raw low-level writes alike
+        if address == 0x02
+            && let Some(ceiling) = self.oc_threshold_ceiling
+        {
+            let field = FieldId::OcAdjSet;
+            let threshold = (reg_data & field.mask()) >> field.bit_offset();
+            if OcAdjSet::try_from(threshold as u8).is_ok_and(|t| t > ceiling) {
+                return Err(DrvError::NotSupported(
+                    "OC threshold above configured ceiling",
+                ));
+            }
+        }
+
+        if self.dry_run {
+            info!("dry run: write {:#04x} <- {:#05x}", address, reg_data);
+            return Ok(());
+        }
+
+        #[cfg(feature = "transaction-timing")]
+        let start = self.clock.map(|now| now());
 
         // Build write command: bit 15 = 0 (write), bits 14:11 = address, bits 10:0 = data
-        let cmd: u16 = ((address as u16 & 0x0F) << 11) | (reg_data & 0x07FF);
-        let cmd_bytes = cmd.to_be_bytes();
+        let cmd = self.protocol.write_command(address, reg_data);
+
+        let spi_bus = self
+            .spi_bus
+            .as_mut()
+            .ok_or(DrvError::NotSupported("SPI bus detached"))?;
+
+        // Execute write transaction; the response belongs to the previous frame
+        transfer_word(spi_bus, &mut self.frames, cmd).await?;
 
-        // Execute write transaction
+        // Status register 1 comes back in the frame after the write (N+1). A read command
+        // for the written register clocks it out, and its own response then carries the
+        // read-back when writes are verified.
+        let read_cmd = self.protocol.read_command(address);
+        let status = transfer_word(spi_bus, &mut self.frames, read_cmd).await?;
+        self.last_write_status = Some(status);
+
+        if self.verify_writes {
+            let response = transfer_word(spi_bus, &mut self.frames, read_cmd).await?;
+            if (response & self.protocol.frame_error_bit()) != 0 {
+                return Err(DrvError::FrameError);
+            }
+            let read_back = response & 0x07FF;
+            let mask = verify_mask(address);
+            if read_back & mask != reg_data & mask {
+                return Err(DrvError::VerifyMismatch {
+                    address,
+                    written: reg_data,
+                    read: read_back,
+                });
+            }
+        }
+
+        #[cfg(feature = "transaction-timing")]
+        if let Some(elapsed) = elapsed_us(self.clock, start) {
+            self.last_transaction_us = Some(elapsed);
+        }
+
+        #[cfg(all(feature = "startup-dump", any(feature = "defmt", feature = "log")))]
+        if !self.startup_dumped {
+            self.startup_dumped = true;
+            log_startup_dump(spi_bus, &mut self.frames, self.protocol, None).await;
+        }
+
+        Ok(())
+    }
+}
+
+/// Read the 11-bit value of a register using the N+1 read sequence
+#[cfg(all(feature = "startup-dump", any(feature = "defmt", feature = "log")))]
+#[bisync]
+async fn read_word<SpiBus, E>(
+    spi_bus: &mut SpiBus,
+    frames: &mut FrameLog,
+    protocol: ProtocolConfig,
+    address: u8,
+) -> Result<u16, DrvError<E>>
+where
+    SpiBus: SpiDevice<Error = E>,
+{
+    let response = read_response(spi_bus, frames, protocol, address).await?;
+
+    // Check for frame error (bit 15 = 1 in response for the DRV8301 layout)
+    if (response & protocol.frame_error_bit()) != 0 {
+        return Err(DrvError::FrameError);
+    }
+
+    Ok(response & 0x07FF)
+}
+
+/// Read registers 0x00 through 0x03, sending each read command in the frame that returns the
+/// previous register's response, and return the 11-bit data words
+#[bisync]
+async fn read_pipelined<SpiBus, E>(
+    spi_bus: &mut SpiBus,
+    frames: &mut FrameLog,
+    protocol: ProtocolConfig,
+) -> Result<[u16; 4], DrvError<E>>
+where
+    SpiBus: SpiDevice<Error = E>,
+{
+    let mut words = [0u16; 4];
+    // The final frame repeats the last command just to clock out its response
+    for (frame, address) in [0x00, 0x01, 0x02, 0x03, 0x03].into_iter().enumerate() {
+        let cmd = protocol.read_command(address);
         let mut response_bytes = [0u8; 2];
-        self.spi_bus
-            .transfer(&mut response_bytes, &cmd_bytes)
+        spi_bus
+            .transfer(&mut response_bytes, &cmd.to_be_bytes())
             .await
             .map_err(DrvError::Spi)?;
+        let response = u16::from_be_bytes(response_bytes);
+        frames.record(cmd, response);
 
-        Ok(())
+        if frame > 0 {
+            if response & protocol.frame_error_bit() != 0 {
+                return Err(DrvError::FrameError);
+            }
+            words[frame - 1] = response & 0x07FF;
+        }
+    }
+    Ok(words)
+}
+
+/// Run the N+1 read sequence and return the raw 16-bit response frame
+#[bisync]
+async fn read_response<SpiBus, E>(
+    spi_bus: &mut SpiBus,
+    frames: &mut FrameLog,
+    protocol: ProtocolConfig,
+    address: u8,
+) -> Result<u16, DrvError<E>>
+where
+    SpiBus: SpiDevice<Error = E>,
+{
+    // Build read command: bit 15 = 1 (read), bits 14:11 = address, bits 10:0 = don't care
+    let cmd = protocol.read_command(address);
+
+    // First transaction: send read command
+    transfer_word(spi_bus, frames, cmd).await?;
+
+    // Second transaction: send same command to get actual data (N+1 timing)
+    transfer_word(spi_bus, frames, cmd).await
+}
+
+/// Clock one command frame and return the word received alongside it
+#[bisync]
+async fn transfer_word<SpiBus, E>(
+    spi_bus: &mut SpiBus,
+    frames: &mut FrameLog,
+    cmd: u16,
+) -> Result<u16, DrvError<E>>
+where
+    SpiBus: SpiDevice<Error = E>,
+{
+    let mut response_bytes = [0u8; 2];
+    spi_bus
+        .transfer(&mut response_bytes, &cmd.to_be_bytes())
+        .await
+        .map_err(DrvError::Spi)?;
+    let response = u16::from_be_bytes(response_bytes);
+    frames.record(cmd, response);
+
+    Ok(response)
+}
+
+/// Log every register once, after the first successful transaction (`startup-dump` feature)
+///
+/// Reading status register 1 clears its latched OC bits, so it is only included when the
+/// first transaction was itself a read of it and its value is passed in as `status_1`.
+#[cfg(all(feature = "startup-dump", any(feature = "defmt", feature = "log")))]
+#[bisync]
+async fn log_startup_dump<SpiBus, E>(
+    spi_bus: &mut SpiBus,
+    frames: &mut FrameLog,
+    protocol: ProtocolConfig,
+    status_1: Option<u16>,
+) where
+    SpiBus: SpiDevice<Error = E>,
+{
+    let mut words = [0u16; 3];
+    for (address, word) in (1u8..).zip(words.iter_mut()) {
+        match read_word(spi_bus, frames, protocol, address).await {
+            Ok(value) => *word = value,
+            Err(_) => {
+                warn!("DRV8301 startup register dump failed");
+                return;
+            }
+        }
     }
+    let [status_2, control_1, control_2] = words;
+    match status_1 {
+        Some(status_1) => {
+            let dump = RegisterDump::from_words(status_1, status_2, control_1, control_2);
+            info!("DRV8301 startup register dump: {:?}", dump);
+        }
+        None => info!(
+            "DRV8301 startup register dump (status register 1 not read): status_2 {:#05x}, control_1 {:#05x}, control_2 {:#05x}",
+            status_2, control_1, control_2
+        ),
+    }
+}
+
+/// Microseconds elapsed on `clock` since `start`, saturating at `u32::MAX`
+#[cfg(feature = "transaction-timing")]
+fn elapsed_us(clock: Option<fn() -> u64>, start: Option<u64>) -> Option<u32> {
+    let elapsed = clock?().saturating_sub(start?);
+    Some(u32::try_from(elapsed).unwrap_or(u32::MAX))
+}
+
+/// Unused bits of status register 2 (D10:D8 and D6:D4)
+///
+/// The datasheet gives zero as the value of every status register bit not otherwise set, so
+/// these read 0 on a healthy link.
+const STATUS_REGISTER_2_UNUSED: u16 = 0x0770;
+
+/// Reserved bits of control register 2 (D10:D7)
+///
+/// The datasheet doesn't document what these read back, only that they are reserved, so they
+/// are written as 0 and never checked on reads.
+const CONTROL_REGISTER_2_RESERVED: u16 = 0x0780;
+
+/// Bits a write may set; reserved bits are masked so they are always written as 0
+///
+/// Control register 1 has no reserved bits. The read-back value of the reserved bits of
+/// control register 2 is undocumented, so a read-modify-write clears them here rather than
+/// writing back whatever the part returned.
+fn write_mask(address: u8) -> u16 {
+    match address {
+        0x03 => 0x07FF & !CONTROL_REGISTER_2_RESERVED,
+        _ => 0x07FF,
+    }
+}
+
+/// Bits of status register 1 that a read can clear: FAULT (D10) and the latched FETxx_OC
+/// flags (D5:D0)
+///
+/// The OC status bits latch until the next SPI read command and then clear, so a second read
+/// of a real overcurrent returns them (and FAULT, if nothing else is active) as 0.
+const STATUS_REGISTER_1_CLEAR_ON_READ: u16 = 0x043F;
+
+/// Bits compared between the two reads of double-read validation
+///
+/// Clear-on-read bits are skipped so a latched overcurrent isn't mistaken for corruption and
+/// lost; the first read, which still carries them, is returned.
+fn double_read_mask(address: u8) -> u16 {
+    match address {
+        0x00 => !STATUS_REGISTER_1_CLEAR_ON_READ,
+        _ => 0xFFFF,
+    }
+}
+
+/// Bits compared when verifying a write
+///
+/// GATE_RESET (control register 1, bit 2) self-clears, so it is never expected to read back as written.
+fn verify_mask(address: u8) -> u16 {
+    match address {
+        0x02 => 0x07FF & !0x0004,
+        _ => 0x07FF,
+    }
+}
+
+/// Every DRV8301 register is transferred as a single 16-bit frame
+const FRAME_BYTES: usize = 2;
+
+/// Reject register buffers that don't match the 16-bit frame carrying the 11-bit register
+fn check_buffer_len<E>(len: usize) -> Result<(), DrvError<E>> {
+    if len != FRAME_BYTES {
+        return Err(DrvError::NotSupported("register buffer size mismatch"));
+    }
+    Ok(())
+}
+
+/// Apply the raw field assignments targeting `address` to a control register value
+fn apply_raw_fields<R>(reg: &mut R, address: u8, fields: &[(FieldId, u16)])
+where
+    R: Copy + From<[u8; 2]> + Into<[u8; 2]>,
+{
+    let mut word = u16::from_be_bytes((*reg).into());
+    for &(field, value) in fields
+        .iter()
+        .filter(|(f, _)| f.register_address() == address)
+    {
+        word = (word & !field.mask()) | ((value << field.bit_offset()) & field.mask());
+    }
+    *reg = R::from(word.to_be_bytes());
 }
 
 pub struct Drv8301<
@@ -87,6 +411,8 @@ pub struct Drv8301<
     SpiBusErr: core::fmt::Debug = <SpiImpl as RegisterInterface>::Error,
 > {
     pub ll: DrvLowLevel<SpiImpl>,
+    /// Mirror of the interface's dry-run flag, readable from the generic methods
+    pub(crate) dry_run: bool,
     _marker: core::marker::PhantomData<SpiBusErr>,
 }
 
@@ -96,11 +422,243 @@ where
     E: core::fmt::Debug,
 {
     pub fn new(spi: SpiBus) -> Self {
+        Self::from_interface(DrvInterface::new(spi))
+    }
+
+    /// Wrap an existing interface, keeping its settings
+    pub(crate) fn from_interface(interface: DrvInterface<SpiBus>) -> Self {
         Self {
-            ll: DrvLowLevel::new(DrvInterface::new(spi)),
+            dry_run: interface.dry_run,
+            ll: DrvLowLevel::new(interface),
             _marker: core::marker::PhantomData,
         }
     }
+
+    /// Convert into the async driver, for SPI devices implementing both HAL traits
+    ///
+    /// The SPI device and all interface settings (retries, verification, dry run, protocol,
+    /// OC threshold ceiling) carry over; register contents are untouched.
+    #[only_sync]
+    pub fn into_async(mut self) -> crate::Drv8301Async<DrvInterface<SpiBus>, E>
+    where
+        SpiBus: embedded_hal_async::spi::SpiDevice<Error = E>,
+    {
+        crate::Drv8301Async::from_interface(self.ll.interface().take())
+    }
+
+    /// Convert into the blocking driver, for SPI devices implementing both HAL traits
+    ///
+    /// The SPI device and all interface settings (retries, verification, dry run, protocol,
+    /// OC threshold ceiling) carry over; register contents are untouched.
+    #[only_async]
+    pub fn into_blocking(mut self) -> crate::Drv8301<DrvInterface<SpiBus>, E>
+    where
+        SpiBus: embedded_hal::spi::SpiDevice<Error = E>,
+    {
+        crate::Drv8301::from_interface(self.ll.interface().take())
+    }
+
+    /// Create a driver and apply `config` in one call (blocking driver only)
+    ///
+    /// The control registers are written as by [`apply_config`](Self::apply_config). If an
+    /// access fails the error is returned and the SPI device is dropped with the driver.
+    #[only_sync]
+    pub fn new_with_config(spi: SpiBus, config: &DrvConfig) -> Result<Self, DrvError<E>> {
+        let mut drv = Self::new(spi);
+        drv.apply_config(config)?;
+        Ok(drv)
+    }
+
+    /// Create a driver that reads back every register write and compares it
+    ///
+    /// A write whose read-back differs from the written value fails with
+    /// [`DrvError::VerifyMismatch`]. Each write then costs two extra SPI frames,
+    /// which is worth it during bring-up or in safety-relevant configuration paths.
+    pub fn new_verified(spi: SpiBus) -> Self {
+        let mut drv = Self::new(spi);
+        drv.ll.interface().verify_writes = true;
+        drv
+    }
+
+    /// Retry register reads up to `retries` times when the SPI bus reports an error
+    ///
+    /// Useful for buses that occasionally fail under DMA contention. Retries are issued
+    /// back-to-back, without a delay in between; writes are never retried. Defaults to 0.
+    ///
+    /// If the bus needs time to recover, leave this at 0 and retry at the call site instead,
+    /// delaying before repeating a call that failed with [`DrvError::Spi`].
+    pub fn set_spi_retries(&mut self, retries: u8) {
+        self.ll.interface().spi_retries = retries;
+    }
+
+    /// Set how many consecutive all-ones responses make a read fail with [`DrvError::LinkDown`]
+    ///
+    /// A read that returns all ones (frame error bit included) is repeated, and once
+    /// `frames` such responses arrive in a row the link is reported as down rather than as
+    /// a generic [`DrvError::FrameError`]. 0 disables detection, which is the default so an
+    /// all-ones response keeps failing fast with `FrameError`; 3 is a reasonable opt-in value.
+    pub fn set_link_down_threshold(&mut self, frames: u8) {
+        self.ll.interface().link_down_frames = frames;
+    }
+
+    /// Time every register transaction with `now`, a free-running microsecond clock
+    ///
+    /// The duration of the last successful read or write (including retries and write
+    /// verification) is then available from [`last_transaction_us`](Self::last_transaction_us).
+    #[cfg(feature = "transaction-timing")]
+    pub fn set_clock(&mut self, now: fn() -> u64) {
+        self.ll.interface().clock = Some(now);
+    }
+
+    /// Duration of the last successful register transaction in microseconds
+    ///
+    /// `None` until a clock is set with [`set_clock`](Self::set_clock) and a transaction completes.
+    #[cfg(feature = "transaction-timing")]
+    pub fn last_transaction_us(&mut self) -> Option<u32> {
+        self.ll.interface().last_transaction_us
+    }
+
+    /// Raw status register 1 word the device returned for the most recent register write
+    ///
+    /// The device answers a write with status register 1 in the following frame, which the
+    /// driver clocks with a read of the written register (doubling as the read-back for
+    /// [`new_verified`](Self::new_verified)). `None` until the first write reaches the
+    /// device; dry-run writes don't update it.
+    pub fn last_write_status(&mut self) -> Option<u16> {
+        self.ll.interface().last_write_status
+    }
+
+    /// The most recent SPI frames exchanged with the device, as `(sent, received)` words
+    ///
+    /// Holds the last [`FRAME_HISTORY_LEN`](crate::FRAME_HISTORY_LEN) frames; a register read
+    /// adds two (N+1 protocol), a write one. Iterate with `oldest_ordered()` to dump them in
+    /// the order they were sent.
+    #[cfg(feature = "heapless")]
+    pub fn recent_frames(
+        &mut self,
+    ) -> &heapless::HistoryBuffer<(u16, u16), { crate::FRAME_HISTORY_LEN }> {
+        self.ll.interface().frames.history()
+    }
+
+    /// Enable or disable dry-run mode
+    ///
+    /// In dry-run mode register writes are logged (via `defmt` or `log`) instead of being
+    /// sent to the device, while reads still pass through. Useful for reviewing an init
+    /// sequence on a live board before committing any writes.
+    pub fn set_dry_run(&mut self, enabled: bool) {
+        self.ll.interface().dry_run = enabled;
+        self.dry_run = enabled;
+    }
+
+    /// Read every register twice and fail with [`DrvError::FrameError`] if the reads disagree
+    ///
+    /// The SPI frame has no CRC; comparing two consecutive reads catches transient
+    /// corruption, e.g. on long cables, at the cost of doubling read traffic. A status flag
+    /// changing between the two reads is reported the same way. Disabled by default.
+    ///
+    /// Reading status register 1 clears its latched FETxx_OC bits (and FAULT with them), so
+    /// those bits are left out of the comparison and the first read's values are returned.
+    pub fn set_double_read_validation(&mut self, enabled: bool) {
+        self.ll.interface().double_read = enabled;
+    }
+
+    /// Choose how register buffers that aren't exactly 2 bytes are handled
+    ///
+    /// Strict mode (the default) fails such accesses with [`DrvError::NotSupported`], which
+    /// catches caller bugs in low-level code. In lenient mode a short read buffer is left
+    /// untouched, a short write buffer is zero-padded and extra bytes are ignored.
+    pub fn set_strict_buffers(&mut self, strict: bool) {
+        self.ll.interface().strict_buffers = strict;
+    }
+
+    /// Override the SPI command word layout, for related parts with a different addressing scheme
+    ///
+    /// Defaults to [`ProtocolConfig::DRV8301`].
+    pub fn set_protocol(&mut self, protocol: ProtocolConfig) {
+        self.ll.interface().protocol = protocol;
+    }
+
+    /// Set the pattern sent in the don't-care bits 10:0 of every read command
+    ///
+    /// Some buses idle better with a specific pattern, and a few clone parts latch these
+    /// bits. Only the low 11 bits are used. Defaults to 0.
+    pub fn set_read_dummy(&mut self, pattern: u16) {
+        let interface = self.ll.interface();
+        interface.protocol = interface.protocol.with_read_dummy(pattern);
+    }
+
+    /// Refuse control register 1 writes that set a VDS threshold above `ceiling`
+    ///
+    /// A policy guard against accidentally programming a dangerously high VDS threshold. The
+    /// check runs in the register write path, so [`set_oc_threshold`](Self::set_oc_threshold),
+    /// [`apply_config`](Self::apply_config) and raw `ll` writes all fail with
+    /// [`DrvError::NotSupported`] without touching the device.
+    pub fn set_max_oc_threshold(&mut self, ceiling: OcAdjSet) {
+        self.ll.interface().oc_threshold_ceiling = Some(ceiling);
+    }
+
+    /// Remove the ceiling set with [`set_max_oc_threshold`](Self::set_max_oc_threshold)
+    pub fn clear_max_oc_threshold(&mut self) {
+        self.ll.interface().oc_threshold_ceiling = None;
+    }
+
+    /// Pre-enable checklist: returns true if overcurrent protection is fully armed
+    ///
+    /// Reads control register 1 and checks that the OCP mode [is protective](OcpMode::is_protective)
+    /// and that the VDS threshold is within the ceiling set with
+    /// [`set_max_oc_threshold`](Self::set_max_oc_threshold), if any. EN_GATE can't be part of
+    /// the check: SPI only works while it is high.
+    #[bisync]
+    pub async fn is_safe_to_enable(&mut self) -> Result<bool, DrvError<E>> {
+        let mut op = self.ll.control_register_1();
+        let ctrl1 = read_internal(&mut op).await?;
+
+        let threshold_ok = self
+            .ll
+            .interface()
+            .oc_threshold_ceiling
+            .is_none_or(|ceiling| ctrl1.oc_adj_set() <= ceiling);
+        Ok(ctrl1.ocp_mode().is_protective() && threshold_ok)
+    }
+
+    /// Temporarily detach the SPI device from the driver
+    ///
+    /// Returns `None` if the bus is already detached. While detached, every register
+    /// operation fails with [`DrvError::NotSupported`]; reattach with [`put_spi`](Self::put_spi).
+    pub fn take_spi(&mut self) -> Option<SpiBus> {
+        self.ll.interface().spi_bus.take()
+    }
+
+    /// Reattach an SPI device previously detached with [`take_spi`](Self::take_spi)
+    pub fn put_spi(&mut self, spi: SpiBus) {
+        self.ll.interface().spi_bus = Some(spi);
+    }
+
+    /// Read all four registers with pipelined commands, decoding them as [`read_all`](Self::read_all)
+    ///
+    /// Each response arrives in the frame after its command, so the next register's read
+    /// command is sent in that same frame: 5 SPI transactions (and awaits, for the async
+    /// driver) instead of the 8 taken by `read_all`, which cuts executor wakeups in polling
+    /// tasks. SPI retries and link-down detection don't apply; a frame error in any
+    /// response fails the whole read.
+    ///
+    /// The frames bypass the register interface, so double-read validation
+    /// ([`set_double_read_validation`](Self::set_double_read_validation)), the
+    /// [strict buffer](Self::set_strict_buffers) check, the `startup-dump` log and
+    /// `transaction-timing` are skipped as well.
+    #[bisync]
+    pub async fn read_all_pipelined(&mut self) -> Result<DeviceState, DrvError<E>> {
+        let interface = self.ll.interface();
+        let spi_bus = interface
+            .spi_bus
+            .as_mut()
+            .ok_or(DrvError::NotSupported("SPI bus detached"))?;
+        let [status_1, status_2, control_1, control_2] =
+            read_pipelined(spi_bus, &mut interface.frames, interface.protocol).await?;
+        Ok(DeviceState::from_words(
+            status_1, status_2, control_1, control_2,
+        ))
+    }
 }
 
 pub trait CurrentDrvDriverInterface<E>:
@@ -122,6 +680,16 @@ where
     SpiImpl: CurrentDrvDriverInterface<SpiBusErr>,
     SpiBusErr: core::fmt::Debug,
 {
+    /// Maximum SPI clock frequency allowed by the datasheet, see [`SpiTiming`]
+    pub const fn recommended_max_hz() -> u32 {
+        SpiTiming::MAX_CLOCK_HZ
+    }
+
+    /// Check an SPI clock frequency against the datasheet maximum and a conservative limit
+    pub const fn check_timing(spi_hz: u32) -> TimingAdvice {
+        TimingAdvice::for_clock(spi_hz)
+    }
+
     /// Check if any fault condition is active
     #[bisync]
     pub async fn has_fault(&mut self) -> Result<bool, DrvError<SpiBusErr>> {
@@ -130,6 +698,28 @@ where
         Ok(status.fault())
     }
 
+    /// Check the master fault bit with minimal overhead, for hot polling loops
+    ///
+    /// Unlike [`has_fault`](Self::has_fault) this skips the field decode and tests bit 10 of
+    /// the raw status register 1 word directly. The DRV8301 answers a read in the following
+    /// frame, so this costs exactly two 16-bit SPI frames.
+    #[bisync]
+    pub async fn has_fault_fast(&mut self) -> Result<bool, DrvError<SpiBusErr>> {
+        let word = self.read_raw(0x00).await?;
+        Ok(word & (1 << 10) != 0)
+    }
+
+    /// Read a register's raw 11-bit data word, bypassing the field decode
+    #[bisync]
+    async fn read_raw(&mut self, address: u8) -> Result<u16, DrvError<SpiBusErr>> {
+        let mut buf = [0u8; 2];
+        self.ll
+            .interface()
+            .read_register(address, 16, &mut buf)
+            .await?;
+        Ok(u16::from_be_bytes(buf))
+    }
+
     /// Get device ID from Status Register 2
     #[bisync]
     pub async fn get_device_id(&mut self) -> Result<u8, DrvError<SpiBusErr>> {
@@ -138,16 +728,136 @@ where
         Ok(status.device_id())
     }
 
+    /// Identify the connected part from its device ID
+    #[bisync]
+    pub async fn identify(&mut self) -> Result<DeviceVariant, DrvError<SpiBusErr>> {
+        let id = self.get_device_id().await?;
+        Ok(DeviceVariant::from_device_id(id))
+    }
+
+    /// Check that the device answers with a well-formed response
+    ///
+    /// Reads status register 2 and fails with [`DrvError::FrameError`] if the frame error bit
+    /// or any of its unused bits (D10:D8, D6:D4) are set, as happens before the SPI interface
+    /// is ready or with MISO floating.
+    #[bisync]
+    pub async fn probe(&mut self) -> Result<(), DrvError<SpiBusErr>> {
+        let status2 = self.read_raw(0x01).await?;
+        if status2 & STATUS_REGISTER_2_UNUSED != 0 {
+            return Err(DrvError::FrameError);
+        }
+        Ok(())
+    }
+
+    /// Returns true if the device answers with a well-formed frame, whatever its device ID
+    ///
+    /// Like [`probe`](Self::probe), but a malformed or all-ones response (a frame error or
+    /// [`DrvError::LinkDown`]) yields `false` instead of an error. SPI bus errors are still
+    /// returned as errors.
+    #[bisync]
+    pub async fn is_responsive(&mut self) -> Result<bool, DrvError<SpiBusErr>> {
+        match self.probe().await {
+            Ok(()) => Ok(true),
+            Err(DrvError::FrameError | DrvError::LinkDown) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Poll [`probe`](Self::probe) every millisecond until it succeeds or `timeout_ms` elapses
+    ///
+    /// Use after driving EN_GATE high, instead of a fixed delay; the datasheet allows up to
+    /// [`SpiTiming::SPI_READY_MAX_MS`] before SPI is ready. On timeout the last probe error
+    /// is returned.
+    #[bisync]
+    pub async fn wait_ready(
+        &mut self,
+        delay: &mut impl DelayNs,
+        timeout_ms: u32,
+    ) -> Result<(), DrvError<SpiBusErr>> {
+        let mut elapsed_ms = 0;
+        loop {
+            match self.probe().await {
+                Ok(()) => return Ok(()),
+                Err(e) if elapsed_ms >= timeout_ms => return Err(e),
+                Err(_) => {}
+            }
+            delay.delay_ms(1).await;
+            elapsed_ms += 1;
+        }
+    }
+
+    /// Heuristically check the SPI link for common wiring and configuration problems
+    ///
+    /// Reads status register 2 twice. Its unused bits (D10:D8 and D6:D4) read 0 on a healthy
+    /// link, and the device ID must read the same twice. A misconfigured SPI mode shifts the
+    /// response by a bit, which breaks one of these checks without producing an error.
+    ///
+    /// The reserved bits of control register 2 are not checked: the datasheet doesn't document
+    /// their read-back value.
+    #[bisync]
+    pub async fn diagnose(&mut self) -> Diagnosis {
+        let reads = [self.read_raw(0x01).await, self.read_raw(0x01).await];
+        if reads.iter().all(|r| r.is_err()) {
+            return Diagnosis::NoResponse;
+        }
+        let [Ok(status2), Ok(status2_again)] = reads else {
+            return Diagnosis::LikelyWrongSpiMode;
+        };
+        if status2 & STATUS_REGISTER_2_UNUSED != 0 || status2 != status2_again {
+            return Diagnosis::LikelyWrongSpiMode;
+        }
+        Diagnosis::Ok
+    }
+
+    /// Read the status registers and return the most severe active fault, if any
+    ///
+    /// See [`Fault::ALL`] for the priority order.
+    #[bisync]
+    pub async fn fault_source(&mut self) -> Result<Option<Fault>, DrvError<SpiBusErr>> {
+        Ok(self.get_fault_status().await?.most_severe())
+    }
+
+    /// Wait for nFAULT to go low or for `timeout_ms` to elapse, whichever comes first (async only)
+    ///
+    /// On a fault the status registers are read and returned as `Some`; on timeout `None` is
+    /// returned. If nFAULT is already low this returns immediately. A pin error is reported
+    /// as [`DrvError::Pin`].
+    #[only_async]
+    pub async fn wait_for_fault_or_timeout(
+        &mut self,
+        nfault: &mut impl embedded_hal_async::digital::Wait,
+        delay: &mut impl DelayNs,
+        timeout_ms: u32,
+    ) -> Result<Option<FaultStatus>, DrvError<SpiBusErr>> {
+        let faulted = {
+            let mut edge = core::pin::pin!(nfault.wait_for_low());
+            let mut timeout = core::pin::pin!(delay.delay_ms(timeout_ms));
+            core::future::poll_fn(|cx| {
+                if let core::task::Poll::Ready(result) = edge.as_mut().poll(cx) {
+                    return core::task::Poll::Ready(result.map(|()| true));
+                }
+                timeout.as_mut().poll(cx).map(|()| Ok(false))
+            })
+            .await
+            .map_err(|_| DrvError::Pin)?
+        };
+
+        if !faulted {
+            return Ok(None);
+        }
+        self.get_fault_status().await.map(Some)
+    }
+
     /// Get complete fault status from both status registers
     ///
     /// Returns a [`FaultStatus`] struct containing all fault flags from the DRV8301.
     /// This includes voltage faults, thermal conditions, and per-phase overcurrent status.
     ///
     /// # Example
-    /// ```rust,no_run
-    /// # use drv8301_dd::Drv8301;
-    /// # let spi = todo!();
-    /// # let mut drv = Drv8301::new(spi);
+    /// ```rust
+    /// # use drv8301_dd::{Drv8301, Drv8301Async, DrvError};
+    /// # fn blocking<S: embedded_hal::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301::new(spi);
     /// let status = drv.get_fault_status()?;
     /// if status.has_overcurrent() {
     ///     // Handle overcurrent condition
@@ -155,7 +865,13 @@ where
     /// if status.phase_a_overcurrent() {
     ///     // Phase A specific handling
     /// }
-    /// # Ok::<(), drv8301_dd::DrvError<()>>(())
+    /// # Ok(())
+    /// # }
+    /// # async fn asynchronous<S: embedded_hal_async::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301Async::new(spi);
+    /// let status = drv.get_fault_status().await?;
+    /// # Ok(())
+    /// # }
     /// ```
     #[bisync]
     pub async fn get_fault_status(&mut self) -> Result<FaultStatus, DrvError<SpiBusErr>> {
@@ -181,19 +897,361 @@ where
         })
     }
 
+    /// Read all four registers into a [`RegisterDump`]
+    #[bisync]
+    pub async fn dump_registers(&mut self) -> Result<RegisterDump, DrvError<SpiBusErr>> {
+        let status_1 = self.read_raw(0x00).await?;
+        let status_2 = self.read_raw(0x01).await?;
+        let control_1 = self.read_raw(0x02).await?;
+        let control_2 = self.read_raw(0x03).await?;
+        Ok(RegisterDump::from_words(
+            status_1, status_2, control_1, control_2,
+        ))
+    }
+
+    /// Read the raw 11-bit data words of control registers 1 and 2
+    #[bisync]
+    pub async fn read_controls_raw(&mut self) -> Result<(u16, u16), DrvError<SpiBusErr>> {
+        let ctrl1 = self.read_raw(0x02).await?;
+        let ctrl2 = self.read_raw(0x03).await?;
+        Ok((ctrl1, ctrl2))
+    }
+
+    /// Read the complete configuration from both control registers
+    #[bisync]
+    pub async fn get_config(&mut self) -> Result<DrvConfig, DrvError<SpiBusErr>> {
+        let mut op1 = self.ll.control_register_1();
+        let ctrl1 = read_internal(&mut op1).await?;
+
+        let mut op2 = self.ll.control_register_2();
+        let ctrl2 = read_internal(&mut op2).await?;
+
+        Ok(DrvConfig::from_registers(&ctrl1, &ctrl2))
+    }
+
+    /// Read all four registers and decode them into a full device snapshot
+    ///
+    /// Equivalent to [`get_fault_status`](Self::get_fault_status) followed by
+    /// [`get_config`](Self::get_config).
+    #[bisync]
+    pub async fn read_all(&mut self) -> Result<DeviceState, DrvError<SpiBusErr>> {
+        let faults = self.get_fault_status().await?;
+        let config = self.get_config().await?;
+        Ok(DeviceState { faults, config })
+    }
+
+    /// Refresh a caller-owned snapshot in place, as [`read_all`](Self::read_all)
+    ///
+    /// Lets a control loop keep one [`DeviceState`] and update it every tick. Each field is
+    /// written as soon as its registers are read, so on error the fields read before the
+    /// failure are fresh and the rest keep their previous values.
+    #[bisync]
+    pub async fn refresh_into(
+        &mut self,
+        state: &mut DeviceState,
+    ) -> Result<(), DrvError<SpiBusErr>> {
+        state.faults = self.get_fault_status().await?;
+        state.config = self.get_config().await?;
+        Ok(())
+    }
+
+    /// Write a complete configuration to both control registers
+    ///
+    /// Both control registers are read first and only those that differ from `config` are
+    /// written, so re-asserting an unchanged configuration (e.g. periodically as a safety
+    /// measure) costs two reads and no write frames. Use [`reinit`](Self::reinit) to force
+    /// both writes. Once the registers match, the applied configuration is logged at info
+    /// level (with the `defmt` or `log` feature).
+    #[bisync]
+    pub async fn apply_config(&mut self, config: &DrvConfig) -> Result<(), DrvError<SpiBusErr>> {
+        let current = self.get_config().await?;
+
+        if current.control_register_1() != config.control_register_1() {
+            let mut op1 = self.ll.control_register_1();
+            write_internal(&mut op1, |r| *r = config.control_register_1()).await?;
+        }
+
+        if current.control_register_2() != config.control_register_2() {
+            let mut op2 = self.ll.control_register_2();
+            write_internal(&mut op2, |r| *r = config.control_register_2()).await?;
+        }
+
+        if self.dry_run {
+            info!("dry run: DRV8301 config not applied: {:?}", config);
+            return Ok(());
+        }
+        info!("DRV8301 config applied: {:?}", config);
+        Ok(())
+    }
+
+    /// Recover after a suspected brownout by rewriting the full configuration
+    ///
+    /// A GVDD or PVDD brownout can silently return the control registers to their reset
+    /// values. The driver keeps no shadow copy of the registers, so both are rewritten
+    /// unconditionally from `config`, with GATE_RESET asserted in the control register 1
+    /// write to clear latched faults. The fresh fault status is returned.
+    #[bisync]
+    pub async fn reinit(&mut self, config: &DrvConfig) -> Result<FaultStatus, DrvError<SpiBusErr>> {
+        let mut op1 = self.ll.control_register_1();
+        write_internal(&mut op1, |r| {
+            *r = config.control_register_1();
+            r.set_gate_reset(true);
+        })
+        .await?;
+
+        let mut op2 = self.ll.control_register_2();
+        write_internal(&mut op2, |r| *r = config.control_register_2()).await?;
+
+        self.get_fault_status().await
+    }
+
+    /// Check for a fault and, if one is active, try to clear it
+    ///
+    /// Reads the status registers; if a fault is active, pulses GATE_RESET, waits 1 ms and
+    /// reads them again. Suited to running on every nFAULT edge. With no active fault no
+    /// reset is issued and `cleared` is true.
+    #[bisync]
+    pub async fn handle_fault(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<FaultOutcome, DrvError<SpiBusErr>> {
+        if self.get_fault_status().await?.is_ok() {
+            return Ok(FaultOutcome {
+                had_fault: false,
+                cleared: true,
+            });
+        }
+
+        self.reset_gate_faults().await?;
+        delay.delay_ms(1).await;
+        let after = self.get_fault_status().await?;
+        Ok(FaultOutcome {
+            had_fault: true,
+            cleared: after.is_ok(),
+        })
+    }
+
+    /// Clear latched faults, then reapply `config` and return the resulting fault status
+    ///
+    /// Pulses GATE_RESET, waits 1 ms for the gate driver to come back up, rewrites both
+    /// control registers and finally reads the status registers. Unlike [`reinit`](Self::reinit),
+    /// the reset and the configuration are separate writes with a settle time in between.
+    #[bisync]
+    pub async fn reset_and_reconfigure(
+        &mut self,
+        config: &DrvConfig,
+        delay: &mut impl DelayNs,
+    ) -> Result<FaultStatus, DrvError<SpiBusErr>> {
+        self.reset_gate_faults().await?;
+        delay.delay_ms(1).await;
+        self.apply_config(config).await?;
+        self.get_fault_status().await
+    }
+
+    /// Apply a configuration, verify it field by field and check the device is fault-free
+    ///
+    /// After writing both control registers they are read back and compared, failing with
+    /// [`DrvError::VerifyMismatch`] on any difference. The status registers are then read and
+    /// an active fault fails with [`DrvError::FaultActive`]. On success the (fault-free)
+    /// status is returned, making this a one-call safe initialization.
+    #[bisync]
+    pub async fn configure_with_verify(
+        &mut self,
+        config: &DrvConfig,
+    ) -> Result<FaultStatus, DrvError<SpiBusErr>> {
+        self.apply_config(config).await?;
+
+        let (ctrl1, ctrl2) = self.read_controls_raw().await?;
+        let expected = [
+            (
+                0x02,
+                u16::from_be_bytes(config.control_register_1().into()),
+                ctrl1,
+            ),
+            (
+                0x03,
+                u16::from_be_bytes(config.control_register_2().into()),
+                ctrl2,
+            ),
+        ];
+        for (address, written, read) in expected {
+            if read & write_mask(address) != written {
+                return Err(DrvError::VerifyMismatch {
+                    address,
+                    written,
+                    read,
+                });
+            }
+        }
+
+        let status = self.get_fault_status().await?;
+        if !status.is_ok() {
+            return Err(DrvError::FaultActive(status));
+        }
+        Ok(status)
+    }
+
+    /// Read the configuration, update it with `f` and write back only the registers that changed
+    ///
+    /// # Example
+    /// ```rust
+    /// # use drv8301_dd::{Drv8301, Drv8301Async, DrvError, ShuntAmplifierGain};
+    /// # fn blocking<S: embedded_hal::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301::new(spi);
+    /// // Only control register 2 is written
+    /// drv.modify_config(|cfg| cfg.gain = ShuntAmplifierGain::Gain40)?;
+    /// # Ok(())
+    /// # }
+    /// # async fn asynchronous<S: embedded_hal_async::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301Async::new(spi);
+    /// // Only control register 2 is written
+    /// drv.modify_config(|cfg| cfg.gain = ShuntAmplifierGain::Gain40).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[bisync]
+    pub async fn modify_config(
+        &mut self,
+        f: impl FnOnce(&mut DrvConfig),
+    ) -> Result<(), DrvError<SpiBusErr>> {
+        let current = self.get_config().await?;
+        let mut updated = current;
+        f(&mut updated);
+
+        if updated.control_register_1() != current.control_register_1() {
+            let mut op = self.ll.control_register_1();
+            write_internal(&mut op, |r| *r = updated.control_register_1()).await?;
+        }
+        if updated.control_register_2() != current.control_register_2() {
+            let mut op = self.ll.control_register_2();
+            write_internal(&mut op, |r| *r = updated.control_register_2()).await?;
+        }
+        Ok(())
+    }
+
+    /// Read the complete overcurrent configuration from both control registers
+    #[bisync]
+    pub async fn get_overcurrent_config(
+        &mut self,
+    ) -> Result<OvercurrentConfig, DrvError<SpiBusErr>> {
+        let mut op1 = self.ll.control_register_1();
+        let ctrl1 = read_internal(&mut op1).await?;
+
+        let mut op2 = self.ll.control_register_2();
+        let ctrl2 = read_internal(&mut op2).await?;
+
+        Ok(OvercurrentConfig {
+            ocp_mode: ctrl1.ocp_mode(),
+            oc_adj_set: ctrl1.oc_adj_set(),
+            octw_mode: ctrl2.octw_mode(),
+            oc_toff: ctrl2.oc_toff(),
+        })
+    }
+
+    /// Interpret the nOCTW pin level according to the configured [`OctwMode`]
+    ///
+    /// When the pin reports only one condition the configured mode alone decides the event.
+    /// In [`OctwMode::OtAndOc`] the pin can't tell the two apart, so status register 1 is
+    /// read to disambiguate. A deasserted pin returns an empty event without any SPI traffic.
+    #[bisync]
+    pub async fn interpret_octw(
+        &mut self,
+        pin_asserted: bool,
+    ) -> Result<OctwEvent, DrvError<SpiBusErr>> {
+        if !pin_asserted {
+            return Ok(OctwEvent::default());
+        }
+
+        let mut op = self.ll.control_register_2();
+        let ctrl2 = read_internal(&mut op).await?;
+
+        match ctrl2.octw_mode() {
+            OctwMode::OtOnly => Ok(OctwEvent {
+                overtemp: true,
+                overcurrent: false,
+            }),
+            OctwMode::OcOnly | OctwMode::OcOnlyReserved => Ok(OctwEvent {
+                overtemp: false,
+                overcurrent: true,
+            }),
+            OctwMode::OtAndOc => {
+                let mut op = self.ll.status_register_1();
+                let status = read_internal(&mut op).await?;
+                Ok(OctwEvent {
+                    overtemp: status.otw() || status.otsd(),
+                    overcurrent: status.fetha_oc()
+                        || status.fetla_oc()
+                        || status.fethb_oc()
+                        || status.fetlb_oc()
+                        || status.fethc_oc()
+                        || status.fetlc_oc(),
+                })
+            }
+        }
+    }
+
     /// Set the overcurrent (VDS) threshold
+    ///
+    /// # Example
+    /// ```rust
+    /// # use drv8301_dd::{Drv8301, Drv8301Async, DrvError, OcAdjSet};
+    /// # fn blocking<S: embedded_hal::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301::new(spi);
+    /// // Trip when VDS exceeds 0.250V
+    /// drv.set_oc_threshold(OcAdjSet::Vds250mV)?;
+    /// # Ok(())
+    /// # }
+    /// # async fn asynchronous<S: embedded_hal_async::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301Async::new(spi);
+    /// // Trip when VDS exceeds 0.250V
+    /// drv.set_oc_threshold(OcAdjSet::Vds250mV).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Fails with [`DrvError::NotSupported`] without writing if `threshold` is above the
+    /// ceiling set with [`set_max_oc_threshold`](Self::set_max_oc_threshold).
     #[bisync]
     pub async fn set_oc_threshold(
         &mut self,
         threshold: OcAdjSet,
     ) -> Result<(), DrvError<SpiBusErr>> {
+        debug_assert_fits(threshold as u8, FieldId::OcAdjSet);
         let mut op = self.ll.control_register_1();
         modify_internal(&mut op, |r| r.set_oc_adj_set(threshold)).await
     }
 
+    /// Read the configured VDS threshold and return the drain current at which it trips
+    ///
+    /// See [`OcAdjSet::current_limit_a`]; `rds_on_mohm` is the MOSFET on-resistance in mΩ.
+    #[bisync]
+    pub async fn oc_limit_amps(&mut self, rds_on_mohm: f32) -> Result<f32, DrvError<SpiBusErr>> {
+        let mut op = self.ll.control_register_1();
+        let ctrl1 = read_internal(&mut op).await?;
+        Ok(ctrl1.oc_adj_set().current_limit_a(rds_on_mohm))
+    }
+
     /// Set the overcurrent protection mode
+    ///
+    /// # Example
+    /// ```rust
+    /// # use drv8301_dd::{Drv8301, Drv8301Async, DrvError, OcpMode};
+    /// # fn blocking<S: embedded_hal::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301::new(spi);
+    /// // Shut down the half-bridge and latch the fault on overcurrent
+    /// drv.set_ocp_mode(OcpMode::OcLatchShutdown)?;
+    /// # Ok(())
+    /// # }
+    /// # async fn asynchronous<S: embedded_hal_async::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301Async::new(spi);
+    /// // Shut down the half-bridge and latch the fault on overcurrent
+    /// drv.set_ocp_mode(OcpMode::OcLatchShutdown).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     #[bisync]
     pub async fn set_ocp_mode(&mut self, mode: OcpMode) -> Result<(), DrvError<SpiBusErr>> {
+        debug_assert_fits(mode as u8, FieldId::OcpMode);
         let mut op = self.ll.control_register_1();
         modify_internal(&mut op, |r| r.set_ocp_mode(mode)).await
     }
@@ -202,7 +1260,20 @@ where
     #[bisync]
     pub async fn set_pwm_mode(&mut self, three_pwm: bool) -> Result<(), DrvError<SpiBusErr>> {
         let mut op = self.ll.control_register_1();
-        modify_internal(&mut op, |r| r.set_pwm_mode(three_pwm)).await
+        let mode = if three_pwm {
+            PwmMode::ThreePwm
+        } else {
+            PwmMode::SixPwm
+        };
+        modify_internal(&mut op, |r| r.set_pwm_mode(mode)).await
+    }
+
+    /// Get the configured PWM input mode
+    #[bisync]
+    pub async fn get_pwm_mode(&mut self) -> Result<PwmMode, DrvError<SpiBusErr>> {
+        let mut op = self.ll.control_register_1();
+        let ctrl1 = read_internal(&mut op).await?;
+        Ok(ctrl1.pwm_mode())
     }
 
     /// Reset gate driver faults
@@ -212,29 +1283,142 @@ where
         modify_internal(&mut op, |r| r.set_gate_reset(true)).await
     }
 
+    /// Reset gate driver faults, holding GATE_RESET asserted for `hold_us` before clearing it
+    ///
+    /// For designs that need the reset bit held for a minimum time: GATE_RESET is set, the
+    /// delay elapses, then the bit is written back to 0. Other control register 1 fields are
+    /// preserved by both writes.
+    #[bisync]
+    pub async fn reset_gate_faults_timed(
+        &mut self,
+        delay: &mut impl DelayNs,
+        hold_us: u32,
+    ) -> Result<(), DrvError<SpiBusErr>> {
+        self.reset_gate_faults().await?;
+        delay.delay_us(hold_us).await;
+        let mut op = self.ll.control_register_1();
+        modify_internal(&mut op, |r| r.set_gate_reset(false)).await
+    }
+
+    /// Restore both control registers to their datasheet reset values
+    ///
+    /// Writes only control registers 1 and 2, one write frame each. GATE_RESET is written as
+    /// 0, so this never pulses a gate driver fault reset and latched status is left untouched.
+    #[bisync]
+    pub async fn reset_controls(&mut self) -> Result<(), DrvError<SpiBusErr>> {
+        let mut op = self.ll.control_register_1();
+        write_internal(&mut op, |r| r.set_gate_reset(false)).await?;
+        let mut op = self.ll.control_register_2();
+        write_internal(&mut op, |_| ()).await
+    }
+
+    /// Apply a batch of raw field assignments
+    ///
+    /// Assignments are grouped by register so each affected control register is
+    /// read-modified-written exactly once. Every value is range-checked against its
+    /// field width before anything is written.
+    #[bisync]
+    pub async fn apply_fields(
+        &mut self,
+        fields: &[(FieldId, u16)],
+    ) -> Result<(), DrvError<SpiBusErr>> {
+        if fields
+            .iter()
+            .any(|&(field, value)| value > field.mask() >> field.bit_offset())
+        {
+            return Err(DrvError::NotSupported("field value exceeds field width"));
+        }
+        let touches = |address| fields.iter().any(|(f, _)| f.register_address() == address);
+
+        if touches(0x02) {
+            let mut op = self.ll.control_register_1();
+            modify_internal(&mut op, |r| apply_raw_fields(r, 0x02, fields)).await?;
+        }
+        if touches(0x03) {
+            let mut op = self.ll.control_register_2();
+            modify_internal(&mut op, |r| apply_raw_fields(r, 0x03, fields)).await?;
+        }
+        Ok(())
+    }
+
     /// Set the peak gate drive current
+    ///
+    /// Nothing stops this write while the half-bridges are switching: SPI needs EN_GATE high,
+    /// and the driver can't see the PWM inputs. Hold the PWM inputs idle while changing it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use drv8301_dd::{Drv8301, Drv8301Async, DrvError, GateCurrent};
+    /// # fn blocking<S: embedded_hal::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301::new(spi);
+    /// // 0.7A source / 1.0A sink
+    /// drv.set_gate_current(GateCurrent::Medium)?;
+    /// # Ok(())
+    /// # }
+    /// # async fn asynchronous<S: embedded_hal_async::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301Async::new(spi);
+    /// // 0.7A source / 1.0A sink
+    /// drv.set_gate_current(GateCurrent::Medium).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     #[bisync]
     pub async fn set_gate_current(
         &mut self,
         current: GateCurrent,
     ) -> Result<(), DrvError<SpiBusErr>> {
+        debug_assert_fits(current as u8, FieldId::GateCurrent);
         let mut op = self.ll.control_register_1();
         modify_internal(&mut op, |r| r.set_gate_current(current)).await
     }
 
     /// Set the current shunt amplifier gain
+    ///
+    /// # Example
+    /// ```rust
+    /// # use drv8301_dd::{Drv8301, Drv8301Async, DrvError, ShuntAmplifierGain};
+    /// # fn blocking<S: embedded_hal::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301::new(spi);
+    /// drv.set_shunt_amplifier_gain(ShuntAmplifierGain::Gain20)?;
+    /// # Ok(())
+    /// # }
+    /// # async fn asynchronous<S: embedded_hal_async::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301Async::new(spi);
+    /// drv.set_shunt_amplifier_gain(ShuntAmplifierGain::Gain20).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     #[bisync]
     pub async fn set_shunt_amplifier_gain(
         &mut self,
         gain: ShuntAmplifierGain,
     ) -> Result<(), DrvError<SpiBusErr>> {
+        debug_assert_fits(gain as u8, FieldId::Gain);
         let mut op = self.ll.control_register_2();
         modify_internal(&mut op, |r| r.set_gain(gain)).await
     }
 
     /// Set the nOCTW pin reporting mode
+    ///
+    /// # Example
+    /// ```rust
+    /// # use drv8301_dd::{Drv8301, Drv8301Async, DrvError, OctwMode};
+    /// # fn blocking<S: embedded_hal::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301::new(spi);
+    /// // Only overtemperature is reported on nOCTW
+    /// drv.set_octw_mode(OctwMode::OtOnly)?;
+    /// # Ok(())
+    /// # }
+    /// # async fn asynchronous<S: embedded_hal_async::spi::SpiDevice>(spi: S) -> Result<(), DrvError<S::Error>> {
+    /// let mut drv = Drv8301Async::new(spi);
+    /// // Only overtemperature is reported on nOCTW
+    /// drv.set_octw_mode(OctwMode::OtOnly).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     #[bisync]
     pub async fn set_octw_mode(&mut self, mode: OctwMode) -> Result<(), DrvError<SpiBusErr>> {
+        debug_assert_fits(mode as u8, FieldId::OctwMode);
         let mut op = self.ll.control_register_2();
         modify_internal(&mut op, |r| r.set_octw_mode(mode)).await
     }
@@ -253,6 +1437,46 @@ where
         modify_internal(&mut op, |r| r.set_dc_cal_ch2(enable)).await
     }
 
+    /// Enable or disable DC calibration mode for one shunt amplifier channel
+    ///
+    /// Only the requested channel's DC_CAL bit is changed.
+    #[bisync]
+    pub async fn set_dc_cal(
+        &mut self,
+        channel: CsaChannel,
+        enable: bool,
+    ) -> Result<(), DrvError<SpiBusErr>> {
+        let mut op = self.ll.control_register_2();
+        modify_internal(&mut op, |r| match channel {
+            CsaChannel::Ch1 => r.set_dc_cal_ch1(enable),
+            CsaChannel::Ch2 => r.set_dc_cal_ch2(enable),
+        })
+        .await
+    }
+
+    /// Put one shunt amplifier channel into DC calibration and wait `settle_us` for it to settle
+    ///
+    /// The channel's inputs are shorted internally, so its output can now be sampled as the
+    /// zero-current offset. End calibration with [`finish_channel`](Self::finish_channel);
+    /// the other channel keeps measuring throughout.
+    #[bisync]
+    pub async fn calibrate_channel(
+        &mut self,
+        channel: CsaChannel,
+        delay: &mut impl DelayNs,
+        settle_us: u32,
+    ) -> Result<(), DrvError<SpiBusErr>> {
+        self.set_dc_cal(channel, true).await?;
+        delay.delay_us(settle_us).await;
+        Ok(())
+    }
+
+    /// Return a channel put into DC calibration by [`calibrate_channel`](Self::calibrate_channel) to normal operation
+    #[bisync]
+    pub async fn finish_channel(&mut self, channel: CsaChannel) -> Result<(), DrvError<SpiBusErr>> {
+        self.set_dc_cal(channel, false).await
+    }
+
     /// Set overcurrent off-time control mode
     #[bisync]
     pub async fn set_oc_toff(&mut self, off_time_control: bool) -> Result<(), DrvError<SpiBusErr>> {
@@ -20,28 +20,28 @@
 //!
 //! To use the driver, instantiate `Drv8301` (blocking) or `Drv8301Async` (async) with your SPI bus implementation:
 //!
-//! ```rust,no_run
-//! # use embedded_hal::spi::SpiDevice;
-//! # use drv8301_dd::Drv8301;
-//! let spi_device = todo!();
+//! ```rust
+//! # use drv8301_dd::{Drv8301, DrvError};
+//! # fn example<S: embedded_hal::spi::SpiDevice>(spi_device: S) -> Result<(), DrvError<S::Error>> {
 //! let mut drv = Drv8301::new(spi_device);
 //!
 //! // Check for faults
 //! let has_fault = drv.has_fault()?;
-//! # Ok::<(), drv8301_dd::DrvError<()>>(())
+//! # Ok(())
+//! # }
 //! ```
 //!
 //! For async environments, use `Drv8301Async` (re-exported from the `asynchronous` module):
 //!
-//! ```rust,no_run
-//! # use embedded_hal_async::spi::SpiDevice;
-//! # use drv8301_dd::Drv8301Async;
-//! let spi_device = todo!();
+//! ```rust
+//! # use drv8301_dd::{Drv8301Async, DrvError};
+//! # async fn example<S: embedded_hal_async::spi::SpiDevice>(spi_device: S) -> Result<(), DrvError<S::Error>> {
 //! let mut drv = Drv8301Async::new(spi_device);
 //!
 //! // Check for faults
 //! let has_fault = drv.has_fault().await?;
-//! # Ok::<(), drv8301_dd::DrvError<()>>(())
+//! # Ok(())
+//! # }
 //! ```
 //!
 //! For a detailed register map, please refer to the `device.yaml` file in the
@@ -56,6 +56,15 @@
 #[macro_use]
 pub(crate) mod fmt;
 
+pub mod current_sense;
+pub mod deadtime;
+pub mod pins;
+pub mod prelude;
+#[cfg(feature = "critical-section")]
+pub mod shared;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+
 use thiserror::Error;
 
 device_driver::create_device!(device_name: DrvLowLevel, manifest: "device.yaml");
@@ -67,8 +76,241 @@ pub enum DrvError<SpiErr> {
     Spi(SpiErr),
     #[error("SPI frame error detected in response")]
     FrameError,
+    #[error("SPI link down: repeated all-ones responses")]
+    LinkDown,
     #[error("Feature or specific mode not supported/implemented: {0}")]
     NotSupported(&'static str),
+    #[error("Register {address:#04x} read back {read:#05x} after writing {written:#05x}")]
+    VerifyMismatch {
+        address: u8,
+        written: u16,
+        read: u16,
+    },
+    #[error("Device reported an active fault")]
+    FaultActive(FaultStatus),
+    #[error("GPIO pin error")]
+    Pin,
+}
+
+impl<SpiErr> DrvError<SpiErr> {
+    /// Returns the underlying SPI error, if this is a bus error
+    pub fn into_spi(self) -> Option<SpiErr> {
+        match self {
+            DrvError::Spi(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Implement `From<DrvError<SpiErr>>` for an application error type
+///
+/// Takes the target type, the SPI error type and the variant (or any function) wrapping
+/// the [`DrvError`], so driver calls can be propagated with `?`:
+///
+/// ```rust
+/// # use drv8301_dd::{Drv8301, DrvError, OcAdjSet};
+/// # #[derive(Debug)]
+/// # struct MySpiError;
+/// # impl embedded_hal::spi::Error for MySpiError {
+/// #     fn kind(&self) -> embedded_hal::spi::ErrorKind {
+/// #         embedded_hal::spi::ErrorKind::Other
+/// #     }
+/// # }
+/// enum AppError {
+///     Drv(DrvError<MySpiError>),
+///     Timeout,
+/// }
+///
+/// drv8301_dd::impl_drv_error_from!(AppError, MySpiError, AppError::Drv);
+///
+/// fn bring_up<S>(spi: S) -> Result<(), AppError>
+/// where
+///     S: embedded_hal::spi::SpiDevice<Error = MySpiError>,
+/// {
+///     let mut drv = Drv8301::new(spi);
+///     drv.set_oc_threshold(OcAdjSet::Vds250mV)?;
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! impl_drv_error_from {
+    ($target:ty, $spi_err:ty, $wrap:path) => {
+        impl ::core::convert::From<$crate::DrvError<$spi_err>> for $target {
+            fn from(err: $crate::DrvError<$spi_err>) -> Self {
+                $wrap(err)
+            }
+        }
+    };
+}
+
+/// SPI timing limits from the DRV8301 datasheet (section 6.8)
+///
+/// The driver can't observe bus timing, so these serve as a programmatic reference when
+/// configuring the SPI peripheral. Long traces may need a clock well below the maximum.
+pub struct SpiTiming;
+
+impl SpiTiming {
+    /// Maximum SPI clock frequency (100 ns minimum clock period)
+    pub const MAX_CLOCK_HZ: u32 = 10_000_000;
+    /// Minimum nSCS high time between frames, in nanoseconds
+    ///
+    /// Every register read is two back-to-back frames (the response arrives in frame N+1),
+    /// so the SPI device must deassert nSCS for at least this long between them.
+    pub const MIN_CS_HIGH_NS: u32 = 40;
+    /// Maximum delay from EN_GATE going high until SPI is ready, in milliseconds
+    pub const SPI_READY_MAX_MS: u32 = 10;
+    /// Conservative clock for long traces or flying leads (a rule of thumb, not a datasheet limit)
+    pub const LONG_TRACE_MAX_HZ: u32 = 2_000_000;
+}
+
+/// Advice on an SPI clock frequency, see [`check_timing`](Drv8301::check_timing)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TimingAdvice {
+    /// Comfortably within limits
+    Ok,
+    /// Within the datasheet maximum, but above [`SpiTiming::LONG_TRACE_MAX_HZ`]; lower it if
+    /// the bus runs over long traces or wires and frame errors show up
+    TooFastForLongTrace,
+    /// Above the datasheet maximum [`SpiTiming::MAX_CLOCK_HZ`]
+    ExceedsMax,
+}
+
+impl TimingAdvice {
+    /// Classify an SPI clock frequency
+    pub const fn for_clock(spi_hz: u32) -> Self {
+        if spi_hz > SpiTiming::MAX_CLOCK_HZ {
+            TimingAdvice::ExceedsMax
+        } else if spi_hz > SpiTiming::LONG_TRACE_MAX_HZ {
+            TimingAdvice::TooFastForLongTrace
+        } else {
+            TimingAdvice::Ok
+        }
+    }
+}
+
+/// Build the 16-bit SPI read command word for a register address
+///
+/// Bit 15 = 1 (read), bits 14:11 = address, bits 10:0 = don't care (sent as zero).
+/// This is the exact word the driver sends for every register read.
+pub const fn read_command(address: u8) -> u16 {
+    0x8000 | ((address as u16 & 0x0F) << 11)
+}
+
+/// Build the 16-bit SPI write command word for a register address and 11-bit data value
+///
+/// Bit 15 = 0 (write), bits 14:11 = address, bits 10:0 = data.
+/// This is the exact word the driver sends for every register write.
+pub const fn write_command(address: u8, data: u16) -> u16 {
+    ((address as u16 & 0x0F) << 11) | (data & 0x07FF)
+}
+
+/// SPI command word layout, adjustable for related parts with a different addressing scheme
+///
+/// The default is the DRV8301 layout used by [`read_command`] and [`write_command`]:
+/// a 4-bit address at bits 14:11, the read flag at bit 15 and zero in the don't-care bits.
+/// Other layouts are built with [`new`](Self::new), which rejects any layout whose fields
+/// would overlap each other or the 11 data bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ProtocolConfig {
+    address_mask: u8,
+    read_flag_bit: u8,
+    read_dummy: u16,
+}
+
+impl ProtocolConfig {
+    /// DRV8301 command layout
+    pub const DRV8301: Self = Self {
+        address_mask: 0x0F,
+        read_flag_bit: 15,
+        read_dummy: 0,
+    };
+
+    /// Build a command layout, checking that its fields fit a 16-bit frame
+    ///
+    /// `address_mask` is applied to the register address before it is shifted into the bits
+    /// above the 11 data bits, `read_flag_bit` is the position of the read flag in a read
+    /// command word (and of the frame error flag in a response), and `read_dummy` is sent in
+    /// the don't-care bits 10:0 of a read command (only the low 11 bits are used).
+    pub const fn new(
+        address_mask: u8,
+        read_flag_bit: u8,
+        read_dummy: u16,
+    ) -> Result<Self, ProtocolError> {
+        if read_flag_bit < 11 || read_flag_bit > 15 {
+            return Err(ProtocolError::ReadFlagBit(read_flag_bit));
+        }
+        if (address_mask as u32) << 11 > 0xFFFF {
+            return Err(ProtocolError::AddressMask(address_mask));
+        }
+        if ((address_mask as u16) << 11) & (1 << read_flag_bit) != 0 {
+            return Err(ProtocolError::Overlap {
+                address_mask,
+                read_flag_bit,
+            });
+        }
+        Ok(Self {
+            address_mask,
+            read_flag_bit,
+            read_dummy,
+        })
+    }
+
+    /// Mask applied to the register address before it is shifted into place
+    pub const fn address_mask(&self) -> u8 {
+        self.address_mask
+    }
+
+    /// Bit position of the read flag in a read command word
+    pub const fn read_flag_bit(&self) -> u8 {
+        self.read_flag_bit
+    }
+
+    /// Pattern sent in the don't-care bits 10:0 of a read command word
+    pub const fn read_dummy(&self) -> u16 {
+        self.read_dummy
+    }
+
+    /// Same layout with a different read dummy pattern
+    pub const fn with_read_dummy(self, read_dummy: u16) -> Self {
+        Self { read_dummy, ..self }
+    }
+
+    /// Response bit flagging a frame error, at the same position as the read flag
+    pub const fn frame_error_bit(&self) -> u16 {
+        1 << self.read_flag_bit
+    }
+
+    /// Build the read command word for a register address
+    pub const fn read_command(&self, address: u8) -> u16 {
+        (1 << self.read_flag_bit)
+            | ((address & self.address_mask) as u16) << 11
+            | (self.read_dummy & 0x07FF)
+    }
+
+    /// Build the write command word for a register address and 11-bit data value
+    pub const fn write_command(&self, address: u8, data: u16) -> u16 {
+        ((address & self.address_mask) as u16) << 11 | (data & 0x07FF)
+    }
+}
+
+/// Reasons a command layout is rejected by [`ProtocolConfig::new`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ProtocolError {
+    #[error("Read flag bit {0} is outside bits 15:11")]
+    ReadFlagBit(u8),
+    #[error("Address mask {0:#04x} doesn't fit above the 11 data bits")]
+    AddressMask(u8),
+    #[error("Address mask {address_mask:#04x} overlaps read flag bit {read_flag_bit}")]
+    Overlap { address_mask: u8, read_flag_bit: u8 },
+}
+
+impl Default for ProtocolConfig {
+    fn default() -> Self {
+        Self::DRV8301
+    }
 }
 
 /// Complete fault status from both DRV8301 status registers
@@ -130,6 +372,67 @@ impl FaultStatus {
         !self.fault
     }
 
+    /// Convert into a `Result`, returning `Err(self)` if any fault is active (see [`is_ok`](Self::is_ok))
+    pub fn ok_or_fault(self) -> Result<(), FaultStatus> {
+        if self.is_ok() { Ok(()) } else { Err(self) }
+    }
+
+    /// Copy with the master `fault` bit cleared, leaving only the individual causes
+    ///
+    /// Two states with the same causes then compare equal regardless of the OR bit, e.g.
+    /// when deduplicating fault reports.
+    pub const fn causes_only(&self) -> FaultStatus {
+        FaultStatus {
+            fault: false,
+            ..*self
+        }
+    }
+
+    /// Pack all flags into a `u16`, e.g. for compact fault logs
+    ///
+    /// Bits 10:0 mirror status register 1 and bit 11 holds GVDD_OV; bits 15:12 are 0.
+    ///
+    /// | Bit | Flag | Bit | Flag |
+    /// |-----|------|-----|------|
+    /// | 11 | GVDD_OV | 5 | FETHA_OC |
+    /// | 10 | FAULT | 4 | FETLA_OC |
+    /// | 9 | GVDD_UV | 3 | FETHB_OC |
+    /// | 8 | PVDD_UV | 2 | FETLB_OC |
+    /// | 7 | OTSD | 1 | FETHC_OC |
+    /// | 6 | OTW | 0 | FETLC_OC |
+    pub const fn as_bitmask(&self) -> u16 {
+        (self.gvdd_ov as u16) << 11
+            | (self.fault as u16) << 10
+            | (self.gvdd_uv as u16) << 9
+            | (self.pvdd_uv as u16) << 8
+            | (self.otsd as u16) << 7
+            | (self.otw as u16) << 6
+            | (self.fetha_oc as u16) << 5
+            | (self.fetla_oc as u16) << 4
+            | (self.fethb_oc as u16) << 3
+            | (self.fetlb_oc as u16) << 2
+            | (self.fethc_oc as u16) << 1
+            | self.fetlc_oc as u16
+    }
+
+    /// Unpack flags packed with [`as_bitmask`](Self::as_bitmask); bits 15:12 are ignored
+    pub const fn from_bitmask(bits: u16) -> Self {
+        Self {
+            fault: bits & (1 << 10) != 0,
+            gvdd_uv: bits & (1 << 9) != 0,
+            gvdd_ov: bits & (1 << 11) != 0,
+            pvdd_uv: bits & (1 << 8) != 0,
+            otsd: bits & (1 << 7) != 0,
+            otw: bits & (1 << 6) != 0,
+            fetha_oc: bits & (1 << 5) != 0,
+            fetla_oc: bits & (1 << 4) != 0,
+            fethb_oc: bits & (1 << 3) != 0,
+            fetlb_oc: bits & (1 << 2) != 0,
+            fethc_oc: bits & (1 << 1) != 0,
+            fetlc_oc: bits & 1 != 0,
+        }
+    }
+
     /// Returns true if any phase A FET has an overcurrent fault
     pub fn phase_a_overcurrent(&self) -> bool {
         self.fetha_oc || self.fetla_oc
@@ -144,15 +447,1360 @@ impl FaultStatus {
     pub fn phase_c_overcurrent(&self) -> bool {
         self.fethc_oc || self.fetlc_oc
     }
+
+    /// Returns true if the given fault condition is active
+    pub fn contains(&self, fault: Fault) -> bool {
+        match fault {
+            Fault::GvddUndervoltage => self.gvdd_uv,
+            Fault::GvddOvervoltage => self.gvdd_ov,
+            Fault::PvddUndervoltage => self.pvdd_uv,
+            Fault::OvertempShutdown => self.otsd,
+            Fault::OvertempWarning => self.otw,
+            Fault::PhaseAHighOvercurrent => self.fetha_oc,
+            Fault::PhaseALowOvercurrent => self.fetla_oc,
+            Fault::PhaseBHighOvercurrent => self.fethb_oc,
+            Fault::PhaseBLowOvercurrent => self.fetlb_oc,
+            Fault::PhaseCHighOvercurrent => self.fethc_oc,
+            Fault::PhaseCLowOvercurrent => self.fetlc_oc,
+        }
+    }
+
+    /// Returns true if a gate reset (GATE_RESET or a quick EN_GATE pulse) is the remedy
+    ///
+    /// That is the case when a latched shutdown is active, i.e. OTSD or an overcurrent
+    /// under [`OcpMode::OcLatchShutdown`] in `config`, and no supply fault is present.
+    /// GVDD/PVDD faults need the supply fixed first, and GVDD overvoltage can only be
+    /// cleared by a full EN_GATE reset, so any of them makes this false.
+    pub fn is_recoverable_by_reset(&self, config: &DrvConfig) -> bool {
+        if self.has_voltage_fault() {
+            return false;
+        }
+        let latched_oc = self.has_overcurrent() && config.ocp_mode == OcpMode::OcLatchShutdown;
+        self.otsd || latched_oc
+    }
+
+    /// Most severe active fault condition (see [`Fault::ALL`]), or `None` if none is set
+    pub fn most_severe(&self) -> Option<Fault> {
+        Fault::ALL.into_iter().find(|&fault| self.contains(fault))
+    }
+
+    /// Returns true if either FET of the given phase has an overcurrent fault
+    pub const fn phase_overcurrent(&self, phase: Phase) -> bool {
+        self.high_side_overcurrent(phase) || self.low_side_overcurrent(phase)
+    }
+
+    /// Returns true if the high-side FET of the given phase has an overcurrent fault
+    pub const fn high_side_overcurrent(&self, phase: Phase) -> bool {
+        match phase {
+            Phase::A => self.fetha_oc,
+            Phase::B => self.fethb_oc,
+            Phase::C => self.fethc_oc,
+        }
+    }
+
+    /// Returns true if the low-side FET of the given phase has an overcurrent fault
+    pub const fn low_side_overcurrent(&self, phase: Phase) -> bool {
+        match phase {
+            Phase::A => self.fetla_oc,
+            Phase::B => self.fetlb_oc,
+            Phase::C => self.fetlc_oc,
+        }
+    }
+
+    /// First phase (A, B, C order) where both the high-side and low-side FET report overcurrent
+    ///
+    /// Simultaneous OC on both FETs of a half-bridge strongly suggests shoot-through.
+    pub fn shoot_through_phase(&self) -> Option<Phase> {
+        [
+            (Phase::A, self.fetha_oc && self.fetla_oc),
+            (Phase::B, self.fethb_oc && self.fetlb_oc),
+            (Phase::C, self.fethc_oc && self.fetlc_oc),
+        ]
+        .into_iter()
+        .find_map(|(phase, both)| both.then_some(phase))
+    }
+
+    /// Group the active flags into supply, thermal and overcurrent categories
+    pub const fn categories(&self) -> FaultCategories {
+        let phases = [
+            self.fetha_oc || self.fetla_oc,
+            self.fethb_oc || self.fetlb_oc,
+            self.fethc_oc || self.fetlc_oc,
+        ];
+        FaultCategories {
+            supply: self.gvdd_uv || self.gvdd_ov || self.pvdd_uv,
+            thermal: self.otsd || self.otw,
+            overcurrent: phases[0] || phases[1] || phases[2],
+            phases,
+        }
+    }
+
+    /// List the phases with any overcurrent fault, in A, B, C order
+    #[cfg(feature = "heapless")]
+    pub fn overcurrent_phases(&self) -> heapless::Vec<Phase, 3> {
+        Phase::ALL
+            .into_iter()
+            .filter(|&phase| self.phase_overcurrent(phase))
+            .collect()
+    }
+
+    /// Combine with another snapshot, keeping every flag active in either
+    pub fn merge(&self, other: &FaultStatus) -> FaultStatus {
+        self.combine(other, |a, b| a || b)
+    }
+
+    /// Compare against a previous snapshot, returning the flags that were set and cleared since
+    pub fn diff(&self, previous: &FaultStatus) -> FaultDiff {
+        FaultDiff {
+            set: self.combine(previous, |now, before| now && !before),
+            cleared: self.combine(previous, |now, before| !now && before),
+        }
+    }
+
+    /// Write a compact, human-readable summary of the active flags into `buf`
+    ///
+    /// Produces e.g. `FAULT GVDD_UV FETHA_OC`, or `OK` when nothing is set, and returns
+    /// the number of bytes written. If the summary doesn't fit, `buf` is filled with the
+    /// truncated prefix and `Err(core::fmt::Error)` is returned.
+    pub fn write_summary(&self, buf: &mut [u8]) -> Result<usize, core::fmt::Error> {
+        use core::fmt::Write;
+
+        let mut writer = SliceWriter { buf, len: 0 };
+        let mut any = false;
+        for (name, active) in self.flags() {
+            if active {
+                if any {
+                    writer.write_str(" ")?;
+                }
+                writer.write_str(name)?;
+                any = true;
+            }
+        }
+        if !any {
+            writer.write_str("OK")?;
+        }
+        Ok(writer.len)
+    }
+
+    /// All flags paired with their datasheet names, in status register bit order
+    fn flags(&self) -> [(&'static str, bool); 12] {
+        [
+            ("FAULT", self.fault),
+            ("GVDD_UV", self.gvdd_uv),
+            ("PVDD_UV", self.pvdd_uv),
+            ("OTSD", self.otsd),
+            ("OTW", self.otw),
+            ("FETHA_OC", self.fetha_oc),
+            ("FETLA_OC", self.fetla_oc),
+            ("FETHB_OC", self.fethb_oc),
+            ("FETLB_OC", self.fetlb_oc),
+            ("FETHC_OC", self.fethc_oc),
+            ("FETLC_OC", self.fetlc_oc),
+            ("GVDD_OV", self.gvdd_ov),
+        ]
+    }
+
+    /// Combine two snapshots flag by flag
+    fn combine(&self, other: &FaultStatus, f: impl Fn(bool, bool) -> bool) -> FaultStatus {
+        FaultStatus {
+            fault: f(self.fault, other.fault),
+            gvdd_uv: f(self.gvdd_uv, other.gvdd_uv),
+            gvdd_ov: f(self.gvdd_ov, other.gvdd_ov),
+            pvdd_uv: f(self.pvdd_uv, other.pvdd_uv),
+            otsd: f(self.otsd, other.otsd),
+            otw: f(self.otw, other.otw),
+            fetha_oc: f(self.fetha_oc, other.fetha_oc),
+            fetla_oc: f(self.fetla_oc, other.fetla_oc),
+            fethb_oc: f(self.fethb_oc, other.fethb_oc),
+            fetlb_oc: f(self.fetlb_oc, other.fetlb_oc),
+            fethc_oc: f(self.fethc_oc, other.fethc_oc),
+            fetlc_oc: f(self.fetlc_oc, other.fetlc_oc),
+        }
+    }
+}
+
+/// Same as [`FaultStatus::merge`]
+impl core::ops::BitOr for FaultStatus {
+    type Output = FaultStatus;
+
+    fn bitor(self, rhs: FaultStatus) -> FaultStatus {
+        self.merge(&rhs)
+    }
+}
+
+impl core::ops::BitOrAssign for FaultStatus {
+    fn bitor_assign(&mut self, rhs: FaultStatus) {
+        *self = self.merge(&rhs);
+    }
+}
+
+/// An individual fault condition reported in the status registers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Fault {
+    /// GVDD undervoltage (GVDD_UV)
+    GvddUndervoltage,
+    /// GVDD overvoltage (GVDD_OV)
+    GvddOvervoltage,
+    /// PVDD undervoltage (PVDD_UV)
+    PvddUndervoltage,
+    /// Overtemperature shutdown (OTSD)
+    OvertempShutdown,
+    /// Overtemperature warning (OTW)
+    OvertempWarning,
+    /// Phase A high-side overcurrent (FETHA_OC)
+    PhaseAHighOvercurrent,
+    /// Phase A low-side overcurrent (FETLA_OC)
+    PhaseALowOvercurrent,
+    /// Phase B high-side overcurrent (FETHB_OC)
+    PhaseBHighOvercurrent,
+    /// Phase B low-side overcurrent (FETLB_OC)
+    PhaseBLowOvercurrent,
+    /// Phase C high-side overcurrent (FETHC_OC)
+    PhaseCHighOvercurrent,
+    /// Phase C low-side overcurrent (FETLC_OC)
+    PhaseCLowOvercurrent,
+}
+
+impl Fault {
+    /// Bit of this fault in the [`FaultStatus::as_bitmask`] layout
+    pub(crate) const fn mask(self) -> u16 {
+        1 << match self {
+            Fault::GvddOvervoltage => 11,
+            Fault::GvddUndervoltage => 9,
+            Fault::PvddUndervoltage => 8,
+            Fault::OvertempShutdown => 7,
+            Fault::OvertempWarning => 6,
+            Fault::PhaseAHighOvercurrent => 5,
+            Fault::PhaseALowOvercurrent => 4,
+            Fault::PhaseBHighOvercurrent => 3,
+            Fault::PhaseBLowOvercurrent => 2,
+            Fault::PhaseCHighOvercurrent => 1,
+            Fault::PhaseCLowOvercurrent => 0,
+        }
+    }
+
+    /// Every fault condition, most severe first
+    ///
+    /// GVDD overvoltage comes first as it can only be cleared by an EN_GATE reset, followed
+    /// by the other shutdown conditions, the overcurrent faults and finally the
+    /// overtemperature warning, the only condition that doesn't disable the gate driver.
+    pub const ALL: [Fault; 11] = [
+        Fault::GvddOvervoltage,
+        Fault::OvertempShutdown,
+        Fault::GvddUndervoltage,
+        Fault::PvddUndervoltage,
+        Fault::PhaseAHighOvercurrent,
+        Fault::PhaseALowOvercurrent,
+        Fault::PhaseBHighOvercurrent,
+        Fault::PhaseBLowOvercurrent,
+        Fault::PhaseCHighOvercurrent,
+        Fault::PhaseCLowOvercurrent,
+        Fault::OvertempWarning,
+    ];
+
+    /// Human-readable name, e.g. for display on an HMI
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Fault::GvddUndervoltage => "GVDD undervoltage",
+            Fault::GvddOvervoltage => "GVDD overvoltage",
+            Fault::PvddUndervoltage => "PVDD undervoltage",
+            Fault::OvertempShutdown => "Overtemperature shutdown",
+            Fault::OvertempWarning => "Overtemperature warning",
+            Fault::PhaseAHighOvercurrent => "Phase A high-side overcurrent",
+            Fault::PhaseALowOvercurrent => "Phase A low-side overcurrent",
+            Fault::PhaseBHighOvercurrent => "Phase B high-side overcurrent",
+            Fault::PhaseBLowOvercurrent => "Phase B low-side overcurrent",
+            Fault::PhaseCHighOvercurrent => "Phase C high-side overcurrent",
+            Fault::PhaseCLowOvercurrent => "Phase C low-side overcurrent",
+        }
+    }
+}
+
+/// One of the two current shunt amplifier channels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CsaChannel {
+    /// Channel 1 (SO1 output)
+    Ch1,
+    /// Channel 2 (SO2 output)
+    Ch2,
+}
+
+/// One of the three half-bridge phases
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Phase {
+    A,
+    B,
+    C,
+}
+
+impl Phase {
+    /// All phases, in A, B, C order
+    pub const ALL: [Phase; 3] = [Phase::A, Phase::B, Phase::C];
+}
+
+/// Overcurrent protection and reporting settings spread across both control registers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OvercurrentConfig {
+    /// Action taken on an overcurrent event (control register 1)
+    pub ocp_mode: OcpMode,
+    /// VDS trip threshold (control register 1)
+    pub oc_adj_set: OcAdjSet,
+    /// Conditions reported on the nOCTW pin (control register 2)
+    pub octw_mode: OctwMode,
+    /// Off-time control instead of cycle-by-cycle limiting (control register 2)
+    pub oc_toff: bool,
+}
+
+/// Sticky record of every fault flag seen since the last [`clear`](Self::clear)
+///
+/// Feed it each polled [`FaultStatus`]; a flag stays set here even after the device
+/// register clears, which suits "a fault occurred" indicators.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LatchedFaults {
+    seen: FaultStatus,
+}
+
+impl LatchedFaults {
+    /// Create an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulate a freshly polled status
+    pub fn update(&mut self, status: &FaultStatus) {
+        self.seen = self.seen.merge(status);
+    }
+
+    /// Every flag seen since the last clear
+    pub fn faults(&self) -> FaultStatus {
+        self.seen
+    }
+
+    /// Returns true if any flag has been seen since the last clear
+    pub fn any(&self) -> bool {
+        self.seen != FaultStatus::default()
+    }
+
+    /// Forget all accumulated flags
+    pub fn clear(&mut self) {
+        self.seen = FaultStatus::default();
+    }
+}
+
+/// Counts how often each fault flag toggled over the last `N` polled samples
+///
+/// A marginal gate supply makes GVDD_UV come and go between polls; a flag that toggles
+/// at least `threshold` times within the window is reported as unstable, distinct from
+/// a flag that is steadily set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FlickerDetector<const N: usize> {
+    samples: [u16; N],
+    len: usize,
+    next: usize,
+    threshold: usize,
+}
+
+impl<const N: usize> FlickerDetector<N> {
+    /// Create an empty detector reporting flags with at least `threshold` toggles
+    pub const fn new(threshold: usize) -> Self {
+        Self {
+            samples: [0; N],
+            len: 0,
+            next: 0,
+            threshold,
+        }
+    }
+
+    /// Record a freshly polled status, dropping the oldest sample once the window is full
+    pub const fn update(&mut self, status: &FaultStatus) {
+        if N == 0 {
+            return;
+        }
+        self.samples[self.next] = status.as_bitmask();
+        self.next = (self.next + 1) % N;
+        if self.len < N {
+            self.len += 1;
+        }
+    }
+
+    /// Number of set/clear transitions of `fault` within the window
+    pub const fn toggles(&self, fault: Fault) -> usize {
+        let mask = fault.mask();
+        let oldest = if self.len < N { 0 } else { self.next };
+        let mut count = 0;
+        let mut i = 1;
+        while i < self.len {
+            let prev = self.samples[(oldest + i - 1) % N] & mask;
+            let cur = self.samples[(oldest + i) % N] & mask;
+            if prev != cur {
+                count += 1;
+            }
+            i += 1;
+        }
+        count
+    }
+
+    /// Returns true if `fault` toggled at least `threshold` times within the window
+    pub const fn is_unstable(&self, fault: Fault) -> bool {
+        self.toggles(fault) >= self.threshold
+    }
+
+    /// Returns true if the gate driver supply (GVDD) undervoltage flag is flickering
+    pub const fn unstable_supply(&self) -> bool {
+        self.is_unstable(Fault::GvddUndervoltage)
+    }
+
+    /// Forget all recorded samples
+    pub const fn clear(&mut self) {
+        self.len = 0;
+        self.next = 0;
+    }
+}
+
+/// Health grade of the GVDD charge pump reported by [`ChargePumpMonitor`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChargePumpHealth {
+    /// No GVDD undervoltage or overvoltage seen
+    Healthy,
+    /// Occasional GVDD undervoltage, e.g. the pump sagging under high switching load
+    Marginal,
+    /// GVDD overvoltage, or undervoltage in more than a tenth of the samples
+    Failing,
+}
+
+/// Grades GVDD charge-pump health from repeated GVDD_UV/GVDD_OV reads
+///
+/// Feed it every polled [`FaultStatus`]; occurrences are counted until [`reset`](Self::reset).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChargePumpMonitor {
+    samples: u32,
+    undervoltage: u32,
+    overvoltage: u32,
+}
+
+impl ChargePumpMonitor {
+    /// Create a monitor with no samples
+    pub const fn new() -> Self {
+        Self {
+            samples: 0,
+            undervoltage: 0,
+            overvoltage: 0,
+        }
+    }
+
+    /// Account for a freshly polled status and return the updated grade
+    pub const fn update(&mut self, status: &FaultStatus) -> ChargePumpHealth {
+        self.samples = self.samples.saturating_add(1);
+        if status.gvdd_uv {
+            self.undervoltage = self.undervoltage.saturating_add(1);
+        }
+        if status.gvdd_ov {
+            self.overvoltage = self.overvoltage.saturating_add(1);
+        }
+        self.health()
+    }
+
+    /// Current grade
+    pub const fn health(&self) -> ChargePumpHealth {
+        if self.overvoltage > 0 || self.undervoltage as u64 * 10 > self.samples as u64 {
+            ChargePumpHealth::Failing
+        } else if self.undervoltage > 0 {
+            ChargePumpHealth::Marginal
+        } else {
+            ChargePumpHealth::Healthy
+        }
+    }
+
+    /// Number of samples seen
+    pub const fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Number of samples with GVDD undervoltage set
+    pub const fn undervoltage_count(&self) -> u32 {
+        self.undervoltage
+    }
+
+    /// Number of samples with GVDD overvoltage set
+    pub const fn overvoltage_count(&self) -> u32 {
+        self.overvoltage
+    }
+
+    /// Forget all samples
+    pub const fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Outcome of the [`diagnose`](Drv8301::diagnose) link self-check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Diagnosis {
+    /// Responses are well-formed and consistent
+    Ok,
+    /// The device answers, but with frame errors, set unused bits or inconsistent data,
+    /// typically because the SPI peripheral isn't in mode 1 (CPOL=0, CPHA=1)
+    LikelyWrongSpiMode,
+    /// No valid response at all: bus errors or frame errors on every read
+    NoResponse,
+}
+
+/// Part identified by the device ID field of status register 2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DeviceVariant {
+    /// DRV8301 (device ID `0b0001`)
+    Drv8301,
+    /// Unrecognised device ID, e.g. a floating MISO line or a different part
+    Unknown(u8),
+}
+
+impl DeviceVariant {
+    /// Decode the 4-bit device ID field
+    pub const fn from_device_id(id: u8) -> Self {
+        match id {
+            0x1 => DeviceVariant::Drv8301,
+            other => DeviceVariant::Unknown(other),
+        }
+    }
+
+    /// Marketing part number, or `"unknown"` for unrecognised IDs
+    pub const fn part_number(&self) -> &'static str {
+        match self {
+            DeviceVariant::Drv8301 => "DRV8301",
+            DeviceVariant::Unknown(_) => "unknown",
+        }
+    }
+}
+
+/// Active faults grouped by category, see [`FaultStatus::categories`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FaultCategories {
+    /// GVDD undervoltage/overvoltage or PVDD undervoltage
+    pub supply: bool,
+    /// Overtemperature warning or shutdown
+    pub thermal: bool,
+    /// Overcurrent on any FET
+    pub overcurrent: bool,
+    /// Overcurrent on either FET of each phase, indexed in [`Phase::ALL`] order
+    pub phases: [bool; 3],
+}
+
+impl FaultCategories {
+    /// Returns true if either FET of the given phase has an overcurrent fault
+    pub const fn phase(&self, phase: Phase) -> bool {
+        self.phases[phase as usize]
+    }
+}
+
+/// Result of [`handle_fault`](Drv8301::handle_fault)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FaultOutcome {
+    /// A fault was active before the reset
+    pub had_fault: bool,
+    /// No fault is active any more (also true if there was none to begin with)
+    pub cleared: bool,
+}
+
+/// Conditions signalled by an asserted nOCTW pin
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OctwEvent {
+    /// Overtemperature warning or shutdown
+    pub overtemp: bool,
+    /// Overcurrent on any FET
+    pub overcurrent: bool,
+}
+
+/// `core::fmt::Write` adapter that fills a byte slice and errors once it is full
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl core::fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let n = s.len().min(self.buf.len() - self.len);
+        self.buf[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        if n < s.len() {
+            return Err(core::fmt::Error);
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if `value` fits in `field`, per the widths declared in `device.yaml`
+pub(crate) const fn fits_width(value: u8, field: FieldId) -> bool {
+    (value as u32) >> field.bit_width() == 0
+}
+
+/// Debug-build check that an enum value being encoded fits its field, so an enum outgrowing
+/// its field can't silently spill into the neighbouring bits
+#[track_caller]
+pub(crate) fn debug_assert_fits(value: u8, field: FieldId) {
+    debug_assert!(
+        fits_width(value, field),
+        "value wider than its register field"
+    );
+}
+
+/// Complete configuration held in the DRV8301 control registers
+///
+/// Covers every writable field except GATE_RESET, which is a command rather than a setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DrvConfig {
+    /// Peak gate drive current (control register 1)
+    pub gate_current: GateCurrent,
+    /// PWM input mode (control register 1)
+    pub pwm_mode: PwmMode,
+    /// Overcurrent protection mode (control register 1)
+    pub ocp_mode: OcpMode,
+    /// Overcurrent VDS threshold (control register 1)
+    pub oc_adj_set: OcAdjSet,
+    /// nOCTW reporting mode (control register 2)
+    pub octw_mode: OctwMode,
+    /// Shunt amplifier gain (control register 2)
+    pub gain: ShuntAmplifierGain,
+    /// DC calibration for shunt amplifier channel 1 (control register 2)
+    pub dc_cal_ch1: bool,
+    /// DC calibration for shunt amplifier channel 2 (control register 2)
+    pub dc_cal_ch2: bool,
+    /// Overcurrent off-time control (control register 2)
+    pub oc_toff: bool,
+}
+
+impl DrvConfig {
+    /// Power-on reset configuration: both control registers read 0x000
+    ///
+    /// 1.7 A gate current, 6-PWM mode, cycle-by-cycle current limit at 0.060 V, OT and OC
+    /// reported on nOCTW, 10 V/V gain, DC calibration and off-time control disabled.
+    pub const RESET: Self = Self {
+        gate_current: GateCurrent::High,
+        pwm_mode: PwmMode::SixPwm,
+        ocp_mode: OcpMode::CurrentLimit,
+        oc_adj_set: OcAdjSet::Vds060mV,
+        octw_mode: OctwMode::OtAndOc,
+        gain: ShuntAmplifierGain::Gain10,
+        dc_cal_ch1: false,
+        dc_cal_ch2: false,
+        oc_toff: false,
+    };
+
+    /// Decode a configuration from the two control register values
+    pub(crate) fn from_registers(
+        ctrl1: &field_sets::ControlRegister1,
+        ctrl2: &field_sets::ControlRegister2,
+    ) -> Self {
+        Self {
+            gate_current: ctrl1.gate_current(),
+            pwm_mode: ctrl1.pwm_mode(),
+            ocp_mode: ctrl1.ocp_mode(),
+            oc_adj_set: ctrl1.oc_adj_set(),
+            octw_mode: ctrl2.octw_mode(),
+            gain: ctrl2.gain(),
+            dc_cal_ch1: ctrl2.dc_cal_ch1(),
+            dc_cal_ch2: ctrl2.dc_cal_ch2(),
+            oc_toff: ctrl2.oc_toff(),
+        }
+    }
+
+    /// Encode the control register 1 value, with GATE_RESET deasserted
+    pub(crate) fn control_register_1(&self) -> field_sets::ControlRegister1 {
+        debug_assert_fits(self.gate_current as u8, FieldId::GateCurrent);
+        debug_assert_fits(self.pwm_mode as u8, FieldId::PwmMode);
+        debug_assert_fits(self.ocp_mode as u8, FieldId::OcpMode);
+        debug_assert_fits(self.oc_adj_set as u8, FieldId::OcAdjSet);
+        let mut reg = field_sets::ControlRegister1::new();
+        reg.set_gate_current(self.gate_current);
+        reg.set_pwm_mode(self.pwm_mode);
+        reg.set_ocp_mode(self.ocp_mode);
+        reg.set_oc_adj_set(self.oc_adj_set);
+        reg
+    }
+
+    /// Encode the control register 2 value
+    pub(crate) fn control_register_2(&self) -> field_sets::ControlRegister2 {
+        debug_assert_fits(self.octw_mode as u8, FieldId::OctwMode);
+        debug_assert_fits(self.gain as u8, FieldId::Gain);
+        let mut reg = field_sets::ControlRegister2::new();
+        reg.set_octw_mode(self.octw_mode);
+        reg.set_gain(self.gain);
+        reg.set_dc_cal_ch1(self.dc_cal_ch1);
+        reg.set_dc_cal_ch2(self.dc_cal_ch2);
+        reg.set_oc_toff(self.oc_toff);
+        reg
+    }
+
+    /// Version byte leading every blob written by [`to_bytes`](Self::to_bytes)
+    pub const BYTES_VERSION: u8 = 1;
+
+    /// Length of the blob written by [`to_bytes`](Self::to_bytes)
+    pub const BYTES_LEN: usize = 10;
+
+    /// Encode the configuration as a fixed, versioned byte blob, e.g. for storage in flash
+    ///
+    /// Layout (version 1): the version byte followed by one byte per field in declaration
+    /// order, `gate_current`, `pwm_mode`, `ocp_mode`, `oc_adj_set`, `octw_mode`, `gain`,
+    /// `dc_cal_ch1`, `dc_cal_ch2`, `oc_toff`. Enums hold their register field value and
+    /// flags are 0 or 1.
+    pub const fn to_bytes(&self) -> [u8; Self::BYTES_LEN] {
+        [
+            Self::BYTES_VERSION,
+            self.gate_current as u8,
+            self.pwm_mode as u8,
+            self.ocp_mode as u8,
+            self.oc_adj_set as u8,
+            self.octw_mode as u8,
+            self.gain as u8,
+            self.dc_cal_ch1 as u8,
+            self.dc_cal_ch2 as u8,
+            self.oc_toff as u8,
+        ]
+    }
+
+    /// Decode a blob written by [`to_bytes`](Self::to_bytes)
+    ///
+    /// Fails on a wrong length, an unknown version or any byte that isn't a valid value for
+    /// its field.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConfigError> {
+        let bytes: &[u8; Self::BYTES_LEN] = bytes
+            .try_into()
+            .map_err(|_| ConfigError::Length(bytes.len()))?;
+        if bytes[0] != Self::BYTES_VERSION {
+            return Err(ConfigError::Version(bytes[0]));
+        }
+
+        let field = |index: usize| bytes[index];
+        let invalid = |index: usize| ConfigError::InvalidField {
+            index,
+            value: bytes[index],
+        };
+        let flag = |index: usize| match bytes[index] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid(index)),
+        };
+
+        Ok(Self {
+            gate_current: GateCurrent::try_from(field(1)).map_err(|_| invalid(1))?,
+            pwm_mode: PwmMode::try_from(field(2)).map_err(|_| invalid(2))?,
+            ocp_mode: OcpMode::try_from(field(3)).map_err(|_| invalid(3))?,
+            oc_adj_set: OcAdjSet::try_from(field(4)).map_err(|_| invalid(4))?,
+            octw_mode: OctwMode::try_from(field(5)).map_err(|_| invalid(5))?,
+            gain: ShuntAmplifierGain::try_from(field(6)).map_err(|_| invalid(6))?,
+            dc_cal_ch1: flag(7)?,
+            dc_cal_ch2: flag(8)?,
+            oc_toff: flag(9)?,
+        })
+    }
+}
+
+impl Default for DrvConfig {
+    /// Same as [`DrvConfig::RESET`]
+    fn default() -> Self {
+        Self::RESET
+    }
+}
+
+// Every field of the reset configuration must encode to 0, matching the 0x000 register reset
+// values in `device.yaml`
+const _: () = {
+    let bytes = DrvConfig::RESET.to_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        core::assert!(bytes[i] == 0);
+        i += 1;
+    }
+};
+
+impl TryFrom<&[u8]> for DrvConfig {
+    type Error = ConfigError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+/// Reasons a byte blob can't be decoded by [`DrvConfig::from_bytes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConfigError {
+    #[error("Config blob is {0} bytes, expected {len}", len = DrvConfig::BYTES_LEN)]
+    Length(usize),
+    #[error("Unsupported config blob version {0}")]
+    Version(u8),
+    #[error("Invalid value {value:#04x} at config blob offset {index}")]
+    InvalidField { index: usize, value: u8 },
+}
+
+/// Decoded snapshot of both status and both control registers, see
+/// [`read_all`](Drv8301::read_all)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceState {
+    /// Fault flags from status registers 1 and 2
+    pub faults: FaultStatus,
+    /// Configuration from control registers 1 and 2
+    pub config: DrvConfig,
+}
+
+impl DeviceState {
+    /// Decode the raw 11-bit words of registers 0x00 through 0x03
+    pub(crate) fn from_words(status_1: u16, status_2: u16, control_1: u16, control_2: u16) -> Self {
+        // Status register 1 matches bits 10:0 of the fault bitmask, GVDD_OV is bit 7 of
+        // status register 2
+        let gvdd_ov = (status_2 >> 7) & 1;
+        Self {
+            faults: FaultStatus::from_bitmask(status_1 & 0x07FF | gvdd_ov << 11),
+            config: DrvConfig::from_registers(
+                &field_sets::ControlRegister1::from(control_1.to_be_bytes()),
+                &field_sets::ControlRegister2::from(control_2.to_be_bytes()),
+            ),
+        }
+    }
+}
+
+/// Snapshot of all four DRV8301 registers
+///
+/// Holds the raw 11-bit data words as read from the device, plus decoded values for
+/// the fields most often misconfigured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterDump {
+    /// Status register 1 (0x00)
+    pub status_1: u16,
+    /// Status register 2 (0x01)
+    pub status_2: u16,
+    /// Control register 1 (0x02)
+    pub control_1: u16,
+    /// Control register 2 (0x03)
+    pub control_2: u16,
+    /// PWM input mode decoded from control register 1
+    pub pwm_mode: PwmMode,
+}
+
+impl RegisterDump {
+    /// Build a dump from the raw words of registers 0x00 through 0x03
+    pub(crate) fn from_words(status_1: u16, status_2: u16, control_1: u16, control_2: u16) -> Self {
+        Self {
+            status_1,
+            status_2,
+            control_1,
+            control_2,
+            pwm_mode: field_sets::ControlRegister1::from(control_1.to_be_bytes()).pwm_mode(),
+        }
+    }
+
+    /// List every configurable field whose dumped value differs from `config`
+    ///
+    /// Fields are compared as raw values in [`FieldId::ALL`] order. GATE_RESET is skipped
+    /// since it self-clears.
+    #[cfg(feature = "heapless")]
+    pub fn diff(&self, config: &DrvConfig) -> heapless::Vec<FieldMismatch, 16> {
+        let expected_1 = u16::from_be_bytes(config.control_register_1().into());
+        let expected_2 = u16::from_be_bytes(config.control_register_2().into());
+        FieldId::ALL
+            .into_iter()
+            .filter(|&field| field != FieldId::GateReset)
+            .filter_map(|field| {
+                let (expected, actual) = match field.register_address() {
+                    0x02 => (expected_1, self.control_1),
+                    _ => (expected_2, self.control_2),
+                };
+                let expected = (expected & field.mask()) >> field.bit_offset();
+                let actual = (actual & field.mask()) >> field.bit_offset();
+                (expected != actual).then_some(FieldMismatch {
+                    field,
+                    expected,
+                    actual,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A control register field whose value differs from the expected configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FieldMismatch {
+    /// The mismatching field
+    pub field: FieldId,
+    /// Raw field value from the configuration
+    pub expected: u16,
+    /// Raw field value read from the device
+    pub actual: u16,
+}
+
+/// Fault flags that changed between two [`FaultStatus`] snapshots
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FaultDiff {
+    /// Flags active now that were inactive in the previous snapshot
+    pub set: FaultStatus,
+    /// Flags active in the previous snapshot that are now inactive
+    pub cleared: FaultStatus,
+}
+
+impl OcAdjSet {
+    /// Every threshold setting, in ascending order of trip voltage
+    ///
+    /// Pair with [`threshold_mv`](Self::threshold_mv) to list the choices in a UI.
+    pub const fn all() -> &'static [OcAdjSet] {
+        &Self::ALL
+    }
+
+    const ALL: [OcAdjSet; 32] = [
+        OcAdjSet::Vds060mV,
+        OcAdjSet::Vds068mV,
+        OcAdjSet::Vds076mV,
+        OcAdjSet::Vds086mV,
+        OcAdjSet::Vds097mV,
+        OcAdjSet::Vds109mV,
+        OcAdjSet::Vds123mV,
+        OcAdjSet::Vds138mV,
+        OcAdjSet::Vds155mV,
+        OcAdjSet::Vds175mV,
+        OcAdjSet::Vds197mV,
+        OcAdjSet::Vds222mV,
+        OcAdjSet::Vds250mV,
+        OcAdjSet::Vds282mV,
+        OcAdjSet::Vds317mV,
+        OcAdjSet::Vds358mV,
+        OcAdjSet::Vds403mV,
+        OcAdjSet::Vds454mV,
+        OcAdjSet::Vds511mV,
+        OcAdjSet::Vds576mV,
+        OcAdjSet::Vds648mV,
+        OcAdjSet::Vds730mV,
+        OcAdjSet::Vds822mV,
+        OcAdjSet::Vds926mV,
+        OcAdjSet::Vds1043mV,
+        OcAdjSet::Vds1175mV,
+        OcAdjSet::Vds1324mV,
+        OcAdjSet::Vds1491mV,
+        OcAdjSet::Vds1679mV,
+        OcAdjSet::Vds1892mV,
+        OcAdjSet::Vds2131mV,
+        OcAdjSet::Vds2400mV,
+    ];
+
+    /// Next higher trip voltage, or `None` at 2.400 V
+    pub const fn step_up(self) -> Option<Self> {
+        let index = self as usize + 1;
+        if index < Self::ALL.len() {
+            Some(Self::ALL[index])
+        } else {
+            None
+        }
+    }
+
+    /// Next lower trip voltage, or `None` at 0.060 V
+    pub const fn step_down(self) -> Option<Self> {
+        match (self as usize).checked_sub(1) {
+            Some(index) => Some(Self::ALL[index]),
+            None => None,
+        }
+    }
+
+    /// Drain current in amps at which this threshold trips, for a MOSFET with `rds_on_mohm` mΩ on-resistance
+    ///
+    /// The DRV8301 compares VDS against the threshold, so the limit is `threshold / RDS(on)`.
+    /// Use the on-resistance at the expected operating temperature; it rises with heat.
+    pub fn current_limit_a(self, rds_on_mohm: f32) -> f32 {
+        f32::from(self.threshold_mv()) / rds_on_mohm
+    }
+
+    /// Typical VDS trip threshold in millivolts
+    pub const fn threshold_mv(self) -> u16 {
+        match self {
+            OcAdjSet::Vds060mV => 60,
+            OcAdjSet::Vds068mV => 68,
+            OcAdjSet::Vds076mV => 76,
+            OcAdjSet::Vds086mV => 86,
+            OcAdjSet::Vds097mV => 97,
+            OcAdjSet::Vds109mV => 109,
+            OcAdjSet::Vds123mV => 123,
+            OcAdjSet::Vds138mV => 138,
+            OcAdjSet::Vds155mV => 155,
+            OcAdjSet::Vds175mV => 175,
+            OcAdjSet::Vds197mV => 197,
+            OcAdjSet::Vds222mV => 222,
+            OcAdjSet::Vds250mV => 250,
+            OcAdjSet::Vds282mV => 282,
+            OcAdjSet::Vds317mV => 317,
+            OcAdjSet::Vds358mV => 358,
+            OcAdjSet::Vds403mV => 403,
+            OcAdjSet::Vds454mV => 454,
+            OcAdjSet::Vds511mV => 511,
+            OcAdjSet::Vds576mV => 576,
+            OcAdjSet::Vds648mV => 648,
+            OcAdjSet::Vds730mV => 730,
+            OcAdjSet::Vds822mV => 822,
+            OcAdjSet::Vds926mV => 926,
+            OcAdjSet::Vds1043mV => 1043,
+            OcAdjSet::Vds1175mV => 1175,
+            OcAdjSet::Vds1324mV => 1324,
+            OcAdjSet::Vds1491mV => 1491,
+            OcAdjSet::Vds1679mV => 1679,
+            OcAdjSet::Vds1892mV => 1892,
+            OcAdjSet::Vds2131mV => 2131,
+            OcAdjSet::Vds2400mV => 2400,
+        }
+    }
+}
+
+// `OcAdjSet::all()` must list each of the 32 encodings exactly once, in register order
+const _: () = {
+    let all = OcAdjSet::all();
+    core::assert!(all.len() == 32);
+    let mut i = 0;
+    while i < all.len() {
+        core::assert!(all[i] as usize == i);
+        i += 1;
+    }
+};
+
+/// Thresholds are ordered by their trip voltage rather than by their raw register encoding
+impl PartialOrd for OcAdjSet {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OcAdjSet {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.threshold_mv().cmp(&other.threshold_mv())
+    }
+}
+
+/// Debug wrapper printing a field enum together with its raw register bits
+///
+/// The generated enums derive `Debug`, which shows only the variant name. Wrap a value to
+/// log it as e.g. `OcAdjSet::Vds250mV(0x0c)`, matching raw register dumps.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct WithRaw<T>(pub T);
+
+impl<T: core::fmt::Debug + Copy + Into<u8>> core::fmt::Debug for WithRaw<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let type_name = core::any::type_name::<T>();
+        let short_name = type_name.rsplit("::").next().unwrap_or(type_name);
+        write!(f, "{short_name}::{:?}({:#04x})", self.0, self.0.into())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format + Copy + Into<u8>> defmt::Format for WithRaw<T> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{}({=u8:#04x})", self.0, self.0.into())
+    }
+}
+
+impl OcpMode {
+    /// Returns true if the device takes protective action on overcurrent
+    ///
+    /// Current limit and latch shutdown act on the half-bridge; report-only and
+    /// disabled leave the FETs unprotected.
+    pub const fn is_protective(self) -> bool {
+        matches!(self, OcpMode::CurrentLimit | OcpMode::OcLatchShutdown)
+    }
+}
+
+/// How firmware should respond to an overcurrent fault, depending on the OCP mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RecoveryPolicy {
+    /// The half-bridge stays off until a gate reset (GATE_RESET or a quick EN_GATE pulse)
+    ResetRequired,
+    /// The device limits current cycle by cycle and recovers on its own
+    AutoClear,
+    /// The device takes no action; firmware must reduce the load or shut down itself
+    FirmwareAction,
+}
+
+impl RecoveryPolicy {
+    /// Default recovery policy for an OCP mode
+    ///
+    /// [`OcpMode::OcDisabled`] maps to [`FirmwareAction`](Self::FirmwareAction): with
+    /// detection off, any protection is up to the firmware.
+    pub const fn for_ocp_mode(mode: OcpMode) -> Self {
+        match mode {
+            OcpMode::OcLatchShutdown => RecoveryPolicy::ResetRequired,
+            OcpMode::CurrentLimit => RecoveryPolicy::AutoClear,
+            OcpMode::ReportOnly | OcpMode::OcDisabled => RecoveryPolicy::FirmwareAction,
+        }
+    }
+}
+
+impl ShuntAmplifierGain {
+    /// Amplifier gain in V/V
+    pub const fn volts_per_volt(self) -> u8 {
+        match self {
+            ShuntAmplifierGain::Gain10 => 10,
+            ShuntAmplifierGain::Gain20 => 20,
+            ShuntAmplifierGain::Gain40 => 40,
+            ShuntAmplifierGain::Gain80 => 80,
+        }
+    }
+
+    /// Next higher gain, or `None` at 80 V/V
+    pub const fn next(self) -> Option<Self> {
+        match self {
+            ShuntAmplifierGain::Gain10 => Some(ShuntAmplifierGain::Gain20),
+            ShuntAmplifierGain::Gain20 => Some(ShuntAmplifierGain::Gain40),
+            ShuntAmplifierGain::Gain40 => Some(ShuntAmplifierGain::Gain80),
+            ShuntAmplifierGain::Gain80 => None,
+        }
+    }
+
+    /// Next lower gain, or `None` at 10 V/V
+    pub const fn prev(self) -> Option<Self> {
+        match self {
+            ShuntAmplifierGain::Gain10 => None,
+            ShuntAmplifierGain::Gain20 => Some(ShuntAmplifierGain::Gain10),
+            ShuntAmplifierGain::Gain40 => Some(ShuntAmplifierGain::Gain20),
+            ShuntAmplifierGain::Gain80 => Some(ShuntAmplifierGain::Gain40),
+        }
+    }
+}
+
+/// Gains are ordered by V/V
+impl PartialOrd for ShuntAmplifierGain {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ShuntAmplifierGain {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.volts_per_volt().cmp(&other.volts_per_volt())
+    }
+}
+
+impl GateCurrent {
+    /// Peak gate drive current in milliamps, or `None` for the reserved setting
+    pub const fn peak_ma(self) -> Option<u16> {
+        match self {
+            GateCurrent::High => Some(1700),
+            GateCurrent::Medium => Some(700),
+            GateCurrent::Low => Some(250),
+            GateCurrent::Reserved => None,
+        }
+    }
+
+    /// Next higher drive current, or `None` at 1.7 A (and for the reserved setting)
+    pub const fn next(self) -> Option<Self> {
+        match self {
+            GateCurrent::Low => Some(GateCurrent::Medium),
+            GateCurrent::Medium => Some(GateCurrent::High),
+            GateCurrent::High | GateCurrent::Reserved => None,
+        }
+    }
+
+    /// Next lower drive current, or `None` at 0.25 A (and for the reserved setting)
+    pub const fn prev(self) -> Option<Self> {
+        match self {
+            GateCurrent::High => Some(GateCurrent::Medium),
+            GateCurrent::Medium => Some(GateCurrent::Low),
+            GateCurrent::Low | GateCurrent::Reserved => None,
+        }
+    }
+}
+
+/// Gate currents are ordered by peak current; the reserved setting sorts below all others
+impl PartialOrd for GateCurrent {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GateCurrent {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.peak_ma().cmp(&other.peak_ma())
+    }
+}
+
+/// Configurable fields of the DRV8301 control registers
+///
+/// Useful for tooling that needs to group fields by register, e.g. to batch writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FieldId {
+    /// Overcurrent (VDS) threshold, control register 1
+    OcAdjSet,
+    /// Overcurrent protection mode, control register 1
+    OcpMode,
+    /// PWM input mode, control register 1
+    PwmMode,
+    /// Gate driver fault reset, control register 1
+    GateReset,
+    /// Peak gate drive current, control register 1
+    GateCurrent,
+    /// Overcurrent off-time control, control register 2
+    OcToff,
+    /// Shunt amplifier channel 2 DC calibration, control register 2
+    DcCalCh2,
+    /// Shunt amplifier channel 1 DC calibration, control register 2
+    DcCalCh1,
+    /// Shunt amplifier gain, control register 2
+    Gain,
+    /// nOCTW reporting mode, control register 2
+    OctwMode,
+}
+
+impl FieldId {
+    /// Every field, control register 1 first, each from the most significant bit down
+    pub const ALL: [FieldId; 10] = [
+        FieldId::OcAdjSet,
+        FieldId::OcpMode,
+        FieldId::PwmMode,
+        FieldId::GateReset,
+        FieldId::GateCurrent,
+        FieldId::OcToff,
+        FieldId::DcCalCh2,
+        FieldId::DcCalCh1,
+        FieldId::Gain,
+        FieldId::OctwMode,
+    ];
+
+    /// Address of the control register containing this field
+    pub const fn register_address(self) -> u8 {
+        match self {
+            FieldId::OcAdjSet
+            | FieldId::OcpMode
+            | FieldId::PwmMode
+            | FieldId::GateReset
+            | FieldId::GateCurrent => 0x02,
+            FieldId::OcToff
+            | FieldId::DcCalCh2
+            | FieldId::DcCalCh1
+            | FieldId::Gain
+            | FieldId::OctwMode => 0x03,
+        }
+    }
+
+    /// Position of the field's least significant bit within its register
+    pub const fn bit_offset(self) -> u8 {
+        match self {
+            FieldId::OcAdjSet => 6,
+            FieldId::OcpMode => 4,
+            FieldId::PwmMode => 3,
+            FieldId::GateReset => 2,
+            FieldId::GateCurrent => 0,
+            FieldId::OcToff => 6,
+            FieldId::DcCalCh2 => 5,
+            FieldId::DcCalCh1 => 4,
+            FieldId::Gain => 2,
+            FieldId::OctwMode => 0,
+        }
+    }
+
+    /// Width of the field in bits
+    pub const fn bit_width(self) -> u8 {
+        match self {
+            FieldId::OcAdjSet => 5,
+            FieldId::OcpMode | FieldId::GateCurrent | FieldId::Gain | FieldId::OctwMode => 2,
+            FieldId::PwmMode
+            | FieldId::GateReset
+            | FieldId::OcToff
+            | FieldId::DcCalCh2
+            | FieldId::DcCalCh1 => 1,
+        }
+    }
+
+    /// Mask of the field's bits within its register
+    pub const fn mask(self) -> u16 {
+        ((1 << self.bit_width()) - 1) << self.bit_offset()
+    }
+}
+
+/// Number of SPI frames kept for [`recent_frames`](Drv8301::recent_frames) (`heapless` feature)
+#[cfg(feature = "heapless")]
+pub const FRAME_HISTORY_LEN: usize = 16;
+
+/// Ring buffer of recently exchanged frames; zero-sized without the `heapless` feature
+#[derive(Default)]
+pub(crate) struct FrameLog {
+    #[cfg(feature = "heapless")]
+    frames: heapless::HistoryBuffer<(u16, u16), FRAME_HISTORY_LEN>,
+}
+
+impl FrameLog {
+    /// Record a `(sent, received)` frame pair
+    #[cfg_attr(not(feature = "heapless"), allow(unused_variables))]
+    pub(crate) fn record(&mut self, sent: u16, received: u16) {
+        #[cfg(feature = "heapless")]
+        self.frames.write((sent, received));
+    }
+
+    #[cfg(feature = "heapless")]
+    pub(crate) fn history(&self) -> &heapless::HistoryBuffer<(u16, u16), FRAME_HISTORY_LEN> {
+        &self.frames
+    }
 }
 
 pub struct DrvInterface<SpiBus> {
-    spi_bus: SpiBus,
+    spi_bus: Option<SpiBus>,
+    verify_writes: bool,
+    spi_retries: u8,
+    link_down_frames: u8,
+    dry_run: bool,
+    strict_buffers: bool,
+    double_read: bool,
+    oc_threshold_ceiling: Option<OcAdjSet>,
+    protocol: ProtocolConfig,
+    last_write_status: Option<u16>,
+    frames: FrameLog,
+    // The dump costs extra frames, so it is only taken when something can log it
+    #[cfg(all(feature = "startup-dump", any(feature = "defmt", feature = "log")))]
+    startup_dumped: bool,
+    #[cfg(feature = "transaction-timing")]
+    clock: Option<fn() -> u64>,
+    #[cfg(feature = "transaction-timing")]
+    last_transaction_us: Option<u32>,
 }
 
 impl<SpiBus> DrvInterface<SpiBus> {
     pub fn new(spi_bus: SpiBus) -> Self {
-        Self { spi_bus }
+        Self {
+            spi_bus: Some(spi_bus),
+            verify_writes: false,
+            spi_retries: 0,
+            link_down_frames: 0,
+            dry_run: false,
+            strict_buffers: true,
+            double_read: false,
+            oc_threshold_ceiling: None,
+            protocol: ProtocolConfig::DRV8301,
+            last_write_status: None,
+            frames: FrameLog::default(),
+            #[cfg(all(feature = "startup-dump", any(feature = "defmt", feature = "log")))]
+            startup_dumped: false,
+            #[cfg(feature = "transaction-timing")]
+            clock: None,
+            #[cfg(feature = "transaction-timing")]
+            last_transaction_us: None,
+        }
+    }
+
+    /// Move the bus and settings out, leaving a detached interface behind
+    pub(crate) fn take(&mut self) -> Self {
+        Self {
+            spi_bus: self.spi_bus.take(),
+            frames: core::mem::take(&mut self.frames),
+            ..*self
+        }
     }
 }
 
@@ -160,6 +1808,7 @@ impl<SpiBus> DrvInterface<SpiBus> {
 mod asynchronous {
     use bisync::asynchronous::*;
     use device_driver::AsyncRegisterInterface as RegisterInterface;
+    use embedded_hal_async::delay::DelayNs;
     use embedded_hal_async::spi::SpiDevice;
     mod driver;
     pub use driver::*;
@@ -170,9 +1819,562 @@ pub use asynchronous::Drv8301 as Drv8301Async;
 mod blocking {
     use bisync::synchronous::*;
     use device_driver::RegisterInterface;
+    use embedded_hal::delay::DelayNs;
     use embedded_hal::spi::SpiDevice;
     #[allow(clippy::duplicate_mod)]
     mod driver;
     pub use driver::*;
 }
 pub use blocking::Drv8301;
+
+/// Compile-time check that every high-level method exists on both
+/// [`Drv8301`] and [`Drv8301Async`] with the same arguments and output.
+///
+/// Never called and only built with the tests; a method dropped from either
+/// variant (or diverging in signature) fails the test build here instead of at a
+/// downstream call site.
+#[cfg(test)]
+#[allow(dead_code)]
+fn assert_bisync_parity<S, E, D>(
+    b: &mut Drv8301<DrvInterface<S>, E>,
+    a: &mut Drv8301Async<DrvInterface<S>, E>,
+    delay: &mut D,
+    config: &DrvConfig,
+) where
+    S: embedded_hal::spi::SpiDevice<Error = E> + embedded_hal_async::spi::SpiDevice<Error = E>,
+    E: core::fmt::Debug,
+    D: embedded_hal::delay::DelayNs + embedded_hal_async::delay::DelayNs,
+{
+    fn same<T>(_: T, _: impl core::future::Future<Output = T>) {}
+
+    same(b.has_fault(), a.has_fault());
+    same(b.has_fault_fast(), a.has_fault_fast());
+    same(b.get_device_id(), a.get_device_id());
+    same(b.identify(), a.identify());
+    same(b.probe(), a.probe());
+    same(b.is_responsive(), a.is_responsive());
+    same(b.wait_ready(delay, 10), a.wait_ready(delay, 10));
+    same(b.diagnose(), a.diagnose());
+    same(b.fault_source(), a.fault_source());
+    same(b.get_fault_status(), a.get_fault_status());
+    same(b.dump_registers(), a.dump_registers());
+    same(b.read_controls_raw(), a.read_controls_raw());
+    same(b.get_config(), a.get_config());
+    same(b.read_all(), a.read_all());
+    let mut state = DeviceState {
+        faults: FaultStatus::default(),
+        config: DrvConfig::RESET,
+    };
+    same(b.refresh_into(&mut state), a.refresh_into(&mut state));
+    same(b.read_all_pipelined(), a.read_all_pipelined());
+    same(b.apply_config(config), a.apply_config(config));
+    same(b.reinit(config), a.reinit(config));
+    same(
+        b.reset_and_reconfigure(config, delay),
+        a.reset_and_reconfigure(config, delay),
+    );
+    same(
+        b.configure_with_verify(config),
+        a.configure_with_verify(config),
+    );
+    same(b.modify_config(|_| {}), a.modify_config(|_| {}));
+    same(b.get_overcurrent_config(), a.get_overcurrent_config());
+    same(b.is_safe_to_enable(), a.is_safe_to_enable());
+    same(b.interpret_octw(true), a.interpret_octw(true));
+    same(
+        b.set_oc_threshold(OcAdjSet::Vds060mV),
+        a.set_oc_threshold(OcAdjSet::Vds060mV),
+    );
+    same(b.oc_limit_amps(5.0), a.oc_limit_amps(5.0));
+    same(
+        b.set_ocp_mode(OcpMode::CurrentLimit),
+        a.set_ocp_mode(OcpMode::CurrentLimit),
+    );
+    same(b.set_pwm_mode(true), a.set_pwm_mode(true));
+    same(b.get_pwm_mode(), a.get_pwm_mode());
+    same(b.reset_gate_faults(), a.reset_gate_faults());
+    same(b.handle_fault(delay), a.handle_fault(delay));
+    same(
+        b.reset_gate_faults_timed(delay, 10),
+        a.reset_gate_faults_timed(delay, 10),
+    );
+    same(b.reset_controls(), a.reset_controls());
+    same(b.apply_fields(&[]), a.apply_fields(&[]));
+    same(
+        b.set_gate_current(GateCurrent::Medium),
+        a.set_gate_current(GateCurrent::Medium),
+    );
+    same(
+        b.set_shunt_amplifier_gain(ShuntAmplifierGain::Gain10),
+        a.set_shunt_amplifier_gain(ShuntAmplifierGain::Gain10),
+    );
+    same(
+        b.set_octw_mode(OctwMode::OtAndOc),
+        a.set_octw_mode(OctwMode::OtAndOc),
+    );
+    same(b.set_dc_cal_ch1(false), a.set_dc_cal_ch1(false));
+    same(b.set_dc_cal_ch2(false), a.set_dc_cal_ch2(false));
+    same(
+        b.set_dc_cal(CsaChannel::Ch1, false),
+        a.set_dc_cal(CsaChannel::Ch1, false),
+    );
+    same(
+        b.calibrate_channel(CsaChannel::Ch1, delay, 100),
+        a.calibrate_channel(CsaChannel::Ch1, delay, 100),
+    );
+    same(
+        b.finish_channel(CsaChannel::Ch1),
+        a.finish_channel(CsaChannel::Ch1),
+    );
+    same(b.set_oc_toff(false), a.set_oc_toff(false));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timing_advice_for_clock() {
+        assert_eq!(TimingAdvice::for_clock(1_000_000), TimingAdvice::Ok);
+        assert_eq!(
+            TimingAdvice::for_clock(8_000_000),
+            TimingAdvice::TooFastForLongTrace
+        );
+        assert_eq!(
+            TimingAdvice::for_clock(20_000_000),
+            TimingAdvice::ExceedsMax
+        );
+    }
+
+    // The default layout must produce exactly the words of the free command functions, and a
+    // read dummy pattern must land in the don't-care bits only
+    #[test]
+    fn protocol_config_command_words() {
+        let protocol = ProtocolConfig::DRV8301;
+        for address in 0..0x20 {
+            assert_eq!(protocol.read_command(address), read_command(address));
+            assert_eq!(
+                protocol.write_command(address, 0xFFFF),
+                write_command(address, 0xFFFF)
+            );
+            assert_eq!(
+                protocol.write_command(address, 0x0555),
+                write_command(address, 0x0555)
+            );
+        }
+
+        let dummy = ProtocolConfig::DRV8301.with_read_dummy(0x0555);
+        assert_eq!(dummy.read_command(0x03), read_command(0x03) | 0x0555);
+    }
+
+    #[test]
+    fn protocol_config_rejects_overlapping_layouts() {
+        assert_eq!(
+            ProtocolConfig::new(0x0F, 15, 0),
+            Ok(ProtocolConfig::DRV8301)
+        );
+        assert_eq!(ProtocolConfig::DRV8301.frame_error_bit(), 0x8000);
+        assert_eq!(
+            ProtocolConfig::new(0x0F, 16, 0),
+            Err(ProtocolError::ReadFlagBit(16))
+        );
+        assert_eq!(
+            ProtocolConfig::new(0x0F, 10, 0),
+            Err(ProtocolError::ReadFlagBit(10))
+        );
+        assert_eq!(
+            ProtocolConfig::new(0x3F, 15, 0),
+            Err(ProtocolError::AddressMask(0x3F))
+        );
+        assert_eq!(
+            ProtocolConfig::new(0x1F, 15, 0),
+            Err(ProtocolError::Overlap {
+                address_mask: 0x1F,
+                read_flag_bit: 15
+            })
+        );
+        let narrow = ProtocolConfig::new(0x07, 14, 0).unwrap();
+        assert_eq!(narrow.frame_error_bit(), 0x4000);
+    }
+
+    // Every 12-bit flag pattern must survive a bitmask round trip
+    #[test]
+    fn fault_bitmask_round_trip() {
+        for bits in 0..0x1000 {
+            assert_eq!(FaultStatus::from_bitmask(bits).as_bitmask(), bits);
+        }
+    }
+
+    // Every 11-bit control register word decodes and re-encodes to itself, except GATE_RESET
+    // (control register 1, D2) and the reserved bits (control register 2, D10:D7)
+    #[test]
+    fn control_registers_round_trip() {
+        for word in 0..0x0800u16 {
+            let ctrl1 = field_sets::ControlRegister1::from(word.to_be_bytes());
+            let ctrl2 = field_sets::ControlRegister2::from(word.to_be_bytes());
+            let config = DrvConfig::from_registers(&ctrl1, &ctrl2);
+            assert_eq!(
+                u16::from_be_bytes(config.control_register_1().into()),
+                word & !0x0004,
+                "{word:#05x}"
+            );
+            assert_eq!(
+                u16::from_be_bytes(config.control_register_2().into()),
+                word & 0x007F,
+                "{word:#05x}"
+            );
+        }
+    }
+
+    // Every configuration reachable from a register word survives the byte blob
+    #[test]
+    fn config_bytes_round_trip() {
+        for word in 0..0x0800u16 {
+            let config = DrvConfig::from_registers(
+                &field_sets::ControlRegister1::from(word.to_be_bytes()),
+                &field_sets::ControlRegister2::from(word.to_be_bytes()),
+            );
+            assert_eq!(DrvConfig::from_bytes(&config.to_bytes()), Ok(config));
+        }
+    }
+
+    #[test]
+    fn config_bytes_rejects_bad_blobs() {
+        let good = DrvConfig::RESET.to_bytes();
+        for len in [0, DrvConfig::BYTES_LEN - 1, DrvConfig::BYTES_LEN + 1] {
+            let mut blob = [0u8; DrvConfig::BYTES_LEN + 1];
+            blob[..DrvConfig::BYTES_LEN].copy_from_slice(&good);
+            assert_eq!(
+                DrvConfig::from_bytes(&blob[..len]),
+                Err(ConfigError::Length(len))
+            );
+        }
+        for version in [0, DrvConfig::BYTES_VERSION + 1] {
+            let mut blob = good;
+            blob[0] = version;
+            assert_eq!(
+                DrvConfig::from_bytes(&blob),
+                Err(ConfigError::Version(version))
+            );
+        }
+        // One past the largest value of: pwm_mode, oc_adj_set, gain and a flag
+        for (index, value) in [(2, 2), (4, 32), (6, 4), (9, 2)] {
+            let mut blob = good;
+            blob[index] = value;
+            assert_eq!(
+                DrvConfig::from_bytes(&blob),
+                Err(ConfigError::InvalidField { index, value })
+            );
+        }
+    }
+
+    #[test]
+    fn every_fault_has_description() {
+        for fault in Fault::ALL {
+            assert!(!fault.description().is_empty(), "{fault:?}");
+        }
+    }
+
+    // States differing only in the master bit have the same causes
+    #[test]
+    fn causes_ignore_master_bit() {
+        let cause = Fault::PhaseBHighOvercurrent.mask();
+        let with_master = FaultStatus::from_bitmask(1 << 10 | cause);
+        let without_master = FaultStatus::from_bitmask(cause);
+        assert_ne!(with_master, without_master);
+        assert_eq!(with_master.causes_only(), without_master.causes_only());
+        assert_eq!(with_master.causes_only().as_bitmask(), cause);
+    }
+
+    // Each FET flag is reported for its own phase and side only
+    #[test]
+    fn fet_flags_map_to_own_phase_and_side() {
+        let flags = [
+            (
+                Phase::A,
+                Fault::PhaseAHighOvercurrent,
+                Fault::PhaseALowOvercurrent,
+            ),
+            (
+                Phase::B,
+                Fault::PhaseBHighOvercurrent,
+                Fault::PhaseBLowOvercurrent,
+            ),
+            (
+                Phase::C,
+                Fault::PhaseCHighOvercurrent,
+                Fault::PhaseCLowOvercurrent,
+            ),
+        ];
+        for (phase, high, low) in flags {
+            let high = FaultStatus::from_bitmask(high.mask());
+            let low = FaultStatus::from_bitmask(low.mask());
+            assert!(high.high_side_overcurrent(phase));
+            assert!(!high.low_side_overcurrent(phase));
+            assert!(low.low_side_overcurrent(phase));
+            assert!(!low.high_side_overcurrent(phase));
+
+            for other in Phase::ALL {
+                assert_eq!(high.phase_overcurrent(other), other == phase, "{other:?}");
+                assert_eq!(low.phase_overcurrent(other), other == phase, "{other:?}");
+            }
+        }
+    }
+
+    // A flickering GVDD_UV trips the detector, a steadily set one doesn't
+    #[test]
+    fn flicker_detector_trips_on_flicker_only() {
+        let uv = FaultStatus {
+            fault: true,
+            gvdd_uv: true,
+            ..FaultStatus::from_bitmask(0)
+        };
+        let ok = FaultStatus::from_bitmask(0);
+
+        let mut flicker = FlickerDetector::<8>::new(3);
+        let mut steady = FlickerDetector::<8>::new(3);
+        for i in 0..10 {
+            flicker.update(if i % 2 == 0 { &uv } else { &ok });
+            steady.update(&uv);
+        }
+        assert_eq!(flicker.toggles(Fault::GvddUndervoltage), 7);
+        assert!(flicker.unstable_supply());
+        assert!(!flicker.is_unstable(Fault::PvddUndervoltage));
+        assert_eq!(steady.toggles(Fault::GvddUndervoltage), 0);
+        assert!(!steady.unstable_supply());
+    }
+
+    // Healthy until the first undervoltage, marginal while rare, failing once frequent or on
+    // any overvoltage
+    #[test]
+    fn charge_pump_health_escalates() {
+        let ok = FaultStatus::from_bitmask(0);
+        let uv = FaultStatus::from_bitmask(Fault::GvddUndervoltage.mask());
+        let ov = FaultStatus::from_bitmask(Fault::GvddOvervoltage.mask());
+
+        let mut monitor = ChargePumpMonitor::new();
+        for _ in 0..20 {
+            assert_eq!(monitor.update(&ok), ChargePumpHealth::Healthy);
+        }
+        assert_eq!(monitor.update(&uv), ChargePumpHealth::Marginal);
+        assert_eq!(monitor.update(&uv), ChargePumpHealth::Marginal);
+        assert_eq!(monitor.update(&uv), ChargePumpHealth::Failing);
+
+        monitor.reset();
+        assert_eq!(monitor.health(), ChargePumpHealth::Healthy);
+        assert_eq!(monitor.update(&ov), ChargePumpHealth::Failing);
+    }
+
+    #[test]
+    fn device_variant_from_id() {
+        assert_eq!(DeviceVariant::from_device_id(0x1), DeviceVariant::Drv8301);
+        assert_eq!(
+            DeviceVariant::from_device_id(0xF),
+            DeviceVariant::Unknown(0xF)
+        );
+        assert_eq!(DeviceVariant::from_device_id(0x1).part_number(), "DRV8301");
+        assert_eq!(DeviceVariant::from_device_id(0xF).part_number(), "unknown");
+        assert_eq!(DeviceVariant::Unknown(0).part_number(), "unknown");
+    }
+
+    // A PVDD undervoltage together with a phase B overcurrent lands in supply and overcurrent
+    #[test]
+    fn categories_group_faults() {
+        let status = FaultStatus::from_bitmask(
+            1 << 10 | Fault::PvddUndervoltage.mask() | Fault::PhaseBLowOvercurrent.mask(),
+        );
+        let categories = status.categories();
+        assert!(categories.supply);
+        assert!(!categories.thermal);
+        assert!(categories.overcurrent);
+        for phase in Phase::ALL {
+            assert_eq!(categories.phase(phase), phase == Phase::B, "{phase:?}");
+        }
+    }
+
+    #[test]
+    fn fits_width_checks_field_width() {
+        assert!(fits_width(0b11, FieldId::Gain));
+        assert!(!fits_width(0b100, FieldId::Gain));
+        assert!(fits_width(0b1, FieldId::PwmMode));
+        assert!(!fits_width(0b10, FieldId::PwmMode));
+        assert!(fits_width(0b1_1111, FieldId::OcAdjSet));
+        assert!(!fits_width(0b10_0000, FieldId::OcAdjSet));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "value wider than its register field")]
+    fn oversized_field_value_trips_debug_assertion() {
+        debug_assert_fits(0b10_0000, FieldId::OcAdjSet);
+    }
+
+    // Stepping walks the whole range in ascending trip voltage and stops at both ends
+    #[test]
+    fn oc_adj_set_stepping() {
+        let mut threshold = OcAdjSet::Vds060mV;
+        let mut steps = 0;
+        while let Some(next) = threshold.step_up() {
+            assert!(
+                next.threshold_mv() > threshold.threshold_mv(),
+                "{next:?} after {threshold:?}"
+            );
+            assert_eq!(next.step_down(), Some(threshold));
+            threshold = next;
+            steps += 1;
+        }
+        assert_eq!(steps, 31);
+        assert_eq!(threshold, OcAdjSet::Vds2400mV);
+        assert_eq!(OcAdjSet::Vds060mV.step_down(), None);
+    }
+
+    // Stepping must walk each ladder from minimum to maximum and stop at both ends
+    #[test]
+    fn field_enum_stepping() {
+        assert_eq!(GateCurrent::Low.next(), Some(GateCurrent::Medium));
+        assert_eq!(GateCurrent::Medium.next(), Some(GateCurrent::High));
+        assert_eq!(GateCurrent::High.next(), None);
+        assert_eq!(GateCurrent::Low.prev(), None);
+        assert_eq!(GateCurrent::High.prev(), Some(GateCurrent::Medium));
+
+        let mut gain = ShuntAmplifierGain::Gain10;
+        let mut steps = 0;
+        while let Some(next) = gain.next() {
+            assert!(
+                next.volts_per_volt() > gain.volts_per_volt(),
+                "{next:?} after {gain:?}"
+            );
+            gain = next;
+            steps += 1;
+        }
+        assert_eq!(steps, 3);
+        assert_eq!(gain, ShuntAmplifierGain::Gain80);
+        assert_eq!(ShuntAmplifierGain::Gain10.prev(), None);
+    }
+
+    // Ordering follows the datasheet trip voltage, whatever the raw encoding
+    #[test]
+    fn oc_adj_set_orders_by_threshold_voltage() {
+        for a in OcAdjSet::ALL {
+            for b in OcAdjSet::ALL {
+                assert_eq!(
+                    a.cmp(&b),
+                    a.threshold_mv().cmp(&b.threshold_mv()),
+                    "{a:?} vs {b:?}"
+                );
+            }
+        }
+        assert!(OcAdjSet::Vds2400mV > OcAdjSet::Vds060mV);
+        assert_eq!(OcAdjSet::ALL.iter().max(), Some(&OcAdjSet::Vds2400mV));
+        assert_eq!(OcAdjSet::ALL.iter().min(), Some(&OcAdjSet::Vds060mV));
+    }
+
+    // A dump differing in one field per control register lists exactly those two, with a
+    // set GATE_RESET bit ignored
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn register_dump_diff_lists_differing_fields() {
+        let config = DrvConfig {
+            oc_adj_set: OcAdjSet::Vds250mV,
+            gain: ShuntAmplifierGain::Gain40,
+            ..DrvConfig::RESET
+        };
+        let control_1 = u16::from_be_bytes(config.control_register_1().into());
+        let control_2 = u16::from_be_bytes(config.control_register_2().into());
+
+        let dump = RegisterDump::from_words(0x000, 0x001, control_1 | 0x0004, control_2);
+        assert_eq!(dump.diff(&config), []);
+
+        let drifted = DrvConfig {
+            oc_adj_set: OcAdjSet::Vds403mV,
+            gain: ShuntAmplifierGain::Gain10,
+            ..config
+        };
+        let dump = RegisterDump::from_words(
+            0x000,
+            0x001,
+            u16::from_be_bytes(drifted.control_register_1().into()),
+            u16::from_be_bytes(drifted.control_register_2().into()),
+        );
+        assert_eq!(
+            dump.diff(&config),
+            [
+                FieldMismatch {
+                    field: FieldId::OcAdjSet,
+                    expected: OcAdjSet::Vds250mV as u16,
+                    actual: OcAdjSet::Vds403mV as u16,
+                },
+                FieldMismatch {
+                    field: FieldId::Gain,
+                    expected: ShuntAmplifierGain::Gain40 as u16,
+                    actual: ShuntAmplifierGain::Gain10 as u16,
+                },
+            ]
+        );
+    }
+
+    // OTW clearing while GVDD_UV asserts shows up on both sides of the diff
+    #[test]
+    fn fault_diff_reports_set_and_cleared() {
+        let before = FaultStatus {
+            fault: true,
+            otw: true,
+            ..FaultStatus::default()
+        };
+        let now = FaultStatus {
+            fault: true,
+            gvdd_uv: true,
+            ..FaultStatus::default()
+        };
+        let diff = now.diff(&before);
+        assert_eq!(
+            diff.set,
+            FaultStatus {
+                gvdd_uv: true,
+                ..FaultStatus::default()
+            }
+        );
+        assert_eq!(
+            diff.cleared,
+            FaultStatus {
+                otw: true,
+                ..FaultStatus::default()
+            }
+        );
+        assert_eq!(now.diff(&now), FaultDiff::default());
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct BusError;
+
+    #[derive(Debug)]
+    enum AppError {
+        Drv(DrvError<BusError>),
+    }
+
+    crate::impl_drv_error_from!(AppError, BusError, AppError::Drv);
+
+    #[test]
+    fn drv_error_converts_with_question_mark() {
+        fn driver_call() -> Result<(), DrvError<BusError>> {
+            Err(DrvError::FrameError)
+        }
+        fn app() -> Result<(), AppError> {
+            driver_call()?;
+            Ok(())
+        }
+
+        let err = app().unwrap_err();
+        assert!(
+            matches!(err, AppError::Drv(DrvError::FrameError)),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn into_spi_returns_bus_errors_only() {
+        assert_eq!(DrvError::Spi(BusError).into_spi(), Some(BusError));
+        assert_eq!(DrvError::<BusError>::FrameError.into_spi(), None);
+        assert_eq!(DrvError::<BusError>::Pin.into_spi(), None);
+    }
+}
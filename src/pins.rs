@@ -0,0 +1,163 @@
+//! GPIO helpers for the DRV8301 control pins
+
+use embedded_hal::digital::{InputPin, OutputPin};
+
+/// EN_GATE pin that remembers the level it last drove
+///
+/// `OutputPin` can't be read back, so the enable state is tracked alongside the pin.
+/// Note that SPI is only available 5-10 ms after EN_GATE goes high, and holding it low
+/// for more than 20 µs fully resets the device.
+///
+/// SPI is not supported while EN_GATE is low and the control registers revert to their
+/// defaults after a full reset, so the device can't be reconfigured with the gate driver
+/// disabled. Reconfigure with EN_GATE high and the PWM inputs idle instead; the driver has
+/// no view of the PWM inputs, so keeping them idle is up to the caller.
+pub struct EnGate<P> {
+    pin: P,
+    enabled: bool,
+}
+
+impl<P: OutputPin> EnGate<P> {
+    /// Take ownership of the EN_GATE pin, driving it low (gate driver disabled)
+    ///
+    /// If the gate driver was running, holding EN_GATE low for more than 20 µs fully resets
+    /// the DRV8301 and reverts its control registers to their defaults. Use
+    /// [`new_high`](Self::new_high) to take over a pin that is already enabled.
+    pub fn new_low(mut pin: P) -> Result<Self, P::Error> {
+        pin.set_low()?;
+        Ok(Self {
+            pin,
+            enabled: false,
+        })
+    }
+
+    /// Take ownership of the EN_GATE pin, driving it high (gate driver enabled)
+    ///
+    /// Keeps a running device (and its SPI configuration) untouched when the pin is already
+    /// high. From low, SPI becomes available 5-10 ms later.
+    pub fn new_high(mut pin: P) -> Result<Self, P::Error> {
+        pin.set_high()?;
+        Ok(Self { pin, enabled: true })
+    }
+
+    /// Drive EN_GATE high, enabling the gate driver
+    pub fn enable_gate(&mut self) -> Result<(), P::Error> {
+        self.pin.set_high()?;
+        self.enabled = true;
+        Ok(())
+    }
+
+    /// Drive EN_GATE low, disabling the gate driver
+    pub fn disable_gate(&mut self) -> Result<(), P::Error> {
+        self.pin.set_low()?;
+        self.enabled = false;
+        Ok(())
+    }
+
+    /// Returns true if EN_GATE was last driven high
+    pub fn is_gate_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Release the underlying pin
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+/// Level on the MCU input that means a DRV8301 status line is asserted
+///
+/// nFAULT and nOCTW are active-low open-drain outputs: they need a pull-up (external, or
+/// the MCU's internal one) and read low while asserted. Use `ActiveHigh` only if the
+/// board inverts the signal, e.g. through a buffer or level shifter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PinPolarity {
+    /// Asserted when the line reads low (direct connection)
+    #[default]
+    ActiveLow,
+    /// Asserted when the line reads high (inverted by the board)
+    ActiveHigh,
+}
+
+impl PinPolarity {
+    /// Returns true if a line reading `is_high` is asserted under this polarity
+    pub const fn is_asserted(self, is_high: bool) -> bool {
+        match self {
+            PinPolarity::ActiveLow => !is_high,
+            PinPolarity::ActiveHigh => is_high,
+        }
+    }
+}
+
+/// nFAULT input, asserted while any fault is active
+pub struct FaultPin<P> {
+    pin: P,
+    polarity: PinPolarity,
+}
+
+impl<P: InputPin> FaultPin<P> {
+    /// Wrap an nFAULT input wired directly to the DRV8301 (active low, pulled up)
+    pub fn new(pin: P) -> Self {
+        Self::with_polarity(pin, PinPolarity::ActiveLow)
+    }
+
+    /// Wrap an nFAULT input with an explicit polarity
+    pub fn with_polarity(pin: P, polarity: PinPolarity) -> Self {
+        Self { pin, polarity }
+    }
+
+    /// Returns true if the line signals an active fault
+    pub fn is_fault_asserted(&mut self) -> Result<bool, P::Error> {
+        Ok(self.polarity.is_asserted(self.pin.is_high()?))
+    }
+
+    /// Release the underlying pin
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+/// nOCTW input, asserted on overtemperature and/or overcurrent as set by `OctwMode`
+///
+/// Pass the result of [`is_warning_asserted`](Self::is_warning_asserted) to
+/// [`interpret_octw`](crate::Drv8301::interpret_octw) to find out which condition it is.
+pub struct OctwPin<P> {
+    pin: P,
+    polarity: PinPolarity,
+}
+
+impl<P: InputPin> OctwPin<P> {
+    /// Wrap an nOCTW input wired directly to the DRV8301 (active low, pulled up)
+    pub fn new(pin: P) -> Self {
+        Self::with_polarity(pin, PinPolarity::ActiveLow)
+    }
+
+    /// Wrap an nOCTW input with an explicit polarity
+    pub fn with_polarity(pin: P, polarity: PinPolarity) -> Self {
+        Self { pin, polarity }
+    }
+
+    /// Returns true if the line signals an overtemperature or overcurrent warning
+    pub fn is_warning_asserted(&mut self) -> Result<bool, P::Error> {
+        Ok(self.polarity.is_asserted(self.pin.is_high()?))
+    }
+
+    /// Release the underlying pin
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pin_polarity_is_asserted() {
+        assert!(PinPolarity::ActiveLow.is_asserted(false));
+        assert!(!PinPolarity::ActiveLow.is_asserted(true));
+        assert!(PinPolarity::ActiveHigh.is_asserted(true));
+        assert!(!PinPolarity::ActiveHigh.is_asserted(false));
+    }
+}
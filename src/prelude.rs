@@ -0,0 +1,10 @@
+//! Convenience re-exports of the commonly used driver types
+//!
+//! ```rust
+//! use drv8301_dd::prelude::*;
+//! ```
+
+pub use crate::{
+    Drv8301, Drv8301Async, DrvConfig, DrvError, FaultStatus, GateCurrent, OcAdjSet, OcpMode,
+    OctwMode, PwmMode, ShuntAmplifierGain,
+};
@@ -0,0 +1,43 @@
+//! Sharing one blocking driver between tasks (`critical-section` feature)
+
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// A driver guarded by a critical section, for sharing between tasks or interrupt handlers
+///
+/// Intended for the blocking [`Drv8301`](crate::Drv8301): every access runs inside a
+/// critical section, so keep the closures short (a few SPI frames at most).
+///
+/// ```rust
+/// # use drv8301_dd::Drv8301;
+/// # use drv8301_dd::shared::SharedDrv;
+/// # fn example<S: embedded_hal::spi::SpiDevice>(spi: S) where S::Error: core::fmt::Debug {
+/// let shared = SharedDrv::new(Drv8301::new(spi));
+/// let has_fault = shared.with(|drv| drv.has_fault());
+/// # }
+/// ```
+pub struct SharedDrv<D> {
+    inner: Mutex<RefCell<D>>,
+}
+
+impl<D> SharedDrv<D> {
+    /// Wrap a driver; `const` so it can initialize a `static`
+    pub const fn new(drv: D) -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(drv)),
+        }
+    }
+
+    /// Run `f` with exclusive access to the driver inside a critical section
+    ///
+    /// # Panics
+    /// If called re-entrantly from within `f`.
+    pub fn with<R>(&self, f: impl FnOnce(&mut D) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.inner.borrow_ref_mut(cs)))
+    }
+
+    /// Unwrap the driver
+    pub fn into_inner(self) -> D {
+        self.inner.into_inner().into_inner()
+    }
+}
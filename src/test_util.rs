@@ -0,0 +1,989 @@
+//! Helpers for writing tests against this driver (`test-util` feature)
+
+use core::convert::Infallible;
+use core::fmt;
+
+use embedded_hal::spi::{ErrorType, Operation};
+
+use crate::FaultStatus;
+
+/// Assert that two fault snapshots are equal, panicking with the flags that differ
+///
+/// The panic message lists each mismatching flag with its actual and expected value,
+/// e.g. `fault status mismatch: OTW (actual true, expected false)`.
+///
+/// ```rust,should_panic
+/// # use drv8301_dd::FaultStatus;
+/// # use drv8301_dd::test_util::assert_faults_eq;
+/// let actual = FaultStatus { otw: true, ..Default::default() };
+/// assert_faults_eq(&actual, &FaultStatus::default());
+/// ```
+#[track_caller]
+pub fn assert_faults_eq(actual: &FaultStatus, expected: &FaultStatus) {
+    if actual != expected {
+        core::panic!(
+            "fault status mismatch: {}",
+            FlagMismatch { actual, expected }
+        );
+    }
+}
+
+/// Formats the flags that differ between two fault snapshots
+struct FlagMismatch<'a> {
+    actual: &'a FaultStatus,
+    expected: &'a FaultStatus,
+}
+
+impl fmt::Display for FlagMismatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for ((name, actual), (_, expected)) in
+            self.actual.flags().into_iter().zip(self.expected.flags())
+        {
+            if actual != expected {
+                if !first {
+                    f.write_str(", ")?;
+                }
+                first = false;
+                write!(f, "{name} (actual {actual}, expected {expected})")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// In-memory DRV8301 behind an SPI device, for exercising the driver without hardware
+///
+/// Emulates the N+1 frame protocol for registers 0x00-0x03: a read command's data is clocked
+/// out in the following frame, and the frame after a write carries status register 1.
+/// Writes to the control registers are stored (GATE_RESET self-clears), writes to the status
+/// registers are ignored, and an address above 0x03 answers with a frame error. Status
+/// registers hold whatever they were seeded with unless a scripted sequence is set, see
+/// [`set_status_sequence`](Self::set_status_sequence); no fault behaviour is modelled.
+///
+/// Every command word received is logged, see [`sent`](Self::sent), so tests can check
+/// exactly what the driver put on the bus.
+///
+/// Implements both the blocking and the async `SpiDevice` traits, so it drives
+/// [`Drv8301`](crate::Drv8301) and [`Drv8301Async`](crate::Drv8301Async) alike.
+///
+/// ```rust
+/// # use drv8301_dd::{Drv8301, GateCurrent};
+/// # use drv8301_dd::test_util::MockSpi;
+/// let mut drv = Drv8301::new(MockSpi::new());
+/// drv.set_gate_current(GateCurrent::Low).unwrap();
+/// assert_eq!(drv.get_config().unwrap().gate_current, GateCurrent::Low);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockSpi {
+    registers: [u16; 4],
+    next_response: u16,
+    sent: [u16; MOCK_LOG_LEN],
+    frames: usize,
+    transactions: usize,
+    ignore_writes: bool,
+    status_sequences: [StatusSequence; 2],
+}
+
+/// Number of command words [`MockSpi::sent`] keeps
+pub const MOCK_LOG_LEN: usize = 64;
+
+/// Longest sequence [`MockSpi::set_status_sequence`] accepts
+pub const MOCK_SEQUENCE_LEN: usize = 16;
+
+/// Scripted words for one status register; empty means the register value is used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StatusSequence {
+    words: [u16; MOCK_SEQUENCE_LEN],
+    len: usize,
+    reads: usize,
+}
+
+impl StatusSequence {
+    const EMPTY: Self = Self {
+        words: [0; MOCK_SEQUENCE_LEN],
+        len: 0,
+        reads: 0,
+    };
+
+    /// The word for the current N+1 read: each word covers two read commands
+    fn current(&self) -> Option<u16> {
+        let index = (self.reads / 2).min(self.len.checked_sub(1)?);
+        Some(self.words[index])
+    }
+}
+
+impl MockSpi {
+    /// A DRV8301 after power-up: no faults, device ID 1 and both control registers at 0x000
+    pub const fn new() -> Self {
+        Self::with_registers([0x000, 0x001, 0x000, 0x000])
+    }
+
+    /// A device with the given 11-bit register contents, indexed by address
+    pub const fn with_registers(registers: [u16; 4]) -> Self {
+        Self {
+            registers,
+            next_response: 0,
+            sent: [0; MOCK_LOG_LEN],
+            frames: 0,
+            transactions: 0,
+            ignore_writes: false,
+            status_sequences: [StatusSequence::EMPTY; 2],
+        }
+    }
+
+    /// Current 11-bit register contents, indexed by address
+    pub const fn registers(&self) -> [u16; 4] {
+        self.registers
+    }
+
+    /// Drop every register write, as a device held in reset would
+    ///
+    /// Writes are still answered normally, so only a read-back notices.
+    pub fn set_ignore_writes(&mut self, ignore: bool) {
+        self.ignore_writes = ignore;
+    }
+
+    /// Script the words successive reads of status register 1 return, e.g. a fault that
+    /// clears after a reset
+    ///
+    /// Each N+1 read (two consecutive read commands, as the driver sends them) takes the next
+    /// word, and the last word repeats once the sequence runs out. Double-read validation
+    /// reads twice and so consumes two words. The word is also what follows a write. An empty
+    /// slice goes back to the seeded register value.
+    ///
+    /// # Panics
+    ///
+    /// If `words` is longer than [`MOCK_SEQUENCE_LEN`].
+    pub fn set_status_sequence(&mut self, words: &[u16]) {
+        self.set_sequence(0x00, words);
+    }
+
+    /// Like [`set_status_sequence`](Self::set_status_sequence), for status register 2
+    pub fn set_status_2_sequence(&mut self, words: &[u16]) {
+        self.set_sequence(0x01, words);
+    }
+
+    fn set_sequence(&mut self, address: usize, words: &[u16]) {
+        core::assert!(words.len() <= MOCK_SEQUENCE_LEN, "status sequence too long");
+        let mut sequence = StatusSequence::EMPTY;
+        sequence.words[..words.len()].copy_from_slice(words);
+        sequence.len = words.len();
+        self.status_sequences[address] = sequence;
+    }
+
+    /// Current value of a register, taking scripted status sequences into account
+    fn register(&self, address: usize) -> u16 {
+        self.status_sequences
+            .get(address)
+            .and_then(StatusSequence::current)
+            .unwrap_or(self.registers[address])
+    }
+
+    /// Command words received since creation or the last [`clear_sent`](Self::clear_sent),
+    /// oldest first
+    ///
+    /// Only the first [`MOCK_LOG_LEN`] are kept; [`frames`](Self::frames) keeps counting.
+    pub fn sent(&self) -> &[u16] {
+        &self.sent[..self.frames.min(MOCK_LOG_LEN)]
+    }
+
+    /// Number of frames clocked since creation or the last [`clear_sent`](Self::clear_sent)
+    pub const fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Number of `SpiDevice` transactions (chip select assertions) since creation or the
+    /// last [`clear_sent`](Self::clear_sent)
+    pub const fn transactions(&self) -> usize {
+        self.transactions
+    }
+
+    /// Forget the logged command words and reset the frame and transaction counts
+    pub fn clear_sent(&mut self) {
+        self.frames = 0;
+        self.transactions = 0;
+    }
+
+    /// Clock one 16-bit frame: return the pending response and act on `cmd`
+    fn exchange(&mut self, cmd: u16) -> u16 {
+        if let Some(slot) = self.sent.get_mut(self.frames) {
+            *slot = cmd;
+        }
+        self.frames += 1;
+
+        let response = self.next_response;
+        let address = usize::from((cmd >> 11) & 0x0F);
+        self.next_response = match self.registers.get_mut(address) {
+            None => 0x8000,
+            Some(_) if cmd & 0x8000 != 0 => {
+                let value = self.register(address);
+                if let Some(sequence) = self.status_sequences.get_mut(address) {
+                    sequence.reads += 1;
+                }
+                value
+            }
+            Some(_) if self.ignore_writes => self.register(0x00),
+            Some(register) => {
+                if address == 0x02 {
+                    // GATE_RESET (D2) self-clears
+                    *register = cmd & 0x07FF & !0x0004;
+                } else if address == 0x03 {
+                    *register = cmd & 0x07FF;
+                }
+                self.register(0x00)
+            }
+        };
+        response
+    }
+
+    /// Run each two-byte frame of `write` through the device, storing the responses in `read`
+    fn transfer_frames(&mut self, read: &mut [u8], write: &[u8]) {
+        for (i, frame) in write.chunks(2).enumerate() {
+            let cmd = u16::from_be_bytes([frame[0], frame.get(1).copied().unwrap_or(0)]);
+            let response = self.exchange(cmd).to_be_bytes();
+            for (k, byte) in response.into_iter().enumerate() {
+                if let Some(out) = read.get_mut(2 * i + k) {
+                    *out = byte;
+                }
+            }
+        }
+    }
+
+    fn run(&mut self, operations: &mut [Operation<'_, u8>]) {
+        self.transactions += 1;
+        for operation in operations {
+            match operation {
+                Operation::Read(read) => {
+                    let zeros = [0u8; 2];
+                    for chunk in read.chunks_mut(2) {
+                        self.transfer_frames(chunk, &zeros);
+                    }
+                }
+                Operation::Write(write) => self.transfer_frames(&mut [], write),
+                Operation::Transfer(read, write) => self.transfer_frames(read, write),
+                Operation::TransferInPlace(buf) => {
+                    for chunk in buf.chunks_mut(2) {
+                        let mut write = [0u8; 2];
+                        write[..chunk.len()].copy_from_slice(chunk);
+                        self.transfer_frames(chunk, &write[..chunk.len()]);
+                    }
+                }
+                Operation::DelayNs(_) => {}
+            }
+        }
+    }
+}
+
+impl Default for MockSpi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ErrorType for MockSpi {
+    type Error = Infallible;
+}
+
+impl embedded_hal::spi::SpiDevice for MockSpi {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        self.run(operations);
+        Ok(())
+    }
+}
+
+impl embedded_hal_async::spi::SpiDevice for MockSpi {
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        self.run(operations);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MockSpi;
+    use crate::{
+        CsaChannel, Drv8301, Drv8301Async, DrvConfig, DrvError, DrvInterface, Fault, FaultOutcome,
+        FaultStatus, FieldId, GateCurrent, OcAdjSet, OcpMode, OctwMode, PwmMode,
+        ShuntAmplifierGain, read_command, write_command,
+    };
+    use core::convert::Infallible;
+
+    type Driver = Drv8301<DrvInterface<MockSpi>, Infallible>;
+    type AsyncDriver = Drv8301Async<DrvInterface<MockSpi>, Infallible>;
+    type Setter = fn(&mut Driver) -> Result<(), DrvError<Infallible>>;
+
+    /// The mock behind an async driver
+    fn async_mock(drv: &mut AsyncDriver) -> &mut MockSpi {
+        drv.ll.interface().spi_bus.as_mut().expect("bus attached")
+    }
+
+    /// Every field away from its reset value, so a setter that clobbers one shows up
+    const BASE: DrvConfig = DrvConfig {
+        gate_current: GateCurrent::Medium,
+        pwm_mode: PwmMode::ThreePwm,
+        ocp_mode: OcpMode::OcLatchShutdown,
+        oc_adj_set: OcAdjSet::Vds250mV,
+        octw_mode: OctwMode::OtOnly,
+        gain: ShuntAmplifierGain::Gain40,
+        dc_cal_ch1: true,
+        dc_cal_ch2: true,
+        oc_toff: true,
+    };
+
+    /// The mock behind a blocking driver
+    fn mock(drv: &mut Driver) -> &mut MockSpi {
+        drv.ll.interface().spi_bus.as_mut().expect("bus attached")
+    }
+
+    /// The write commands among the frames the mock received
+    fn writes(mock: &MockSpi) -> Vec<u16> {
+        mock.sent()
+            .iter()
+            .copied()
+            .filter(|cmd| cmd & 0x8000 == 0)
+            .collect()
+    }
+
+    /// Poll a future that never pends (the mock answers immediately)
+    fn block_on<F: core::future::Future>(future: F) -> F::Output {
+        let mut future = core::pin::pin!(future);
+        let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+        loop {
+            if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// A driver past its first transaction, so an enabled startup dump doesn't consume
+    /// scripted status words
+    fn started(spi: MockSpi) -> Driver {
+        let mut drv = Driver::new(spi);
+        drv.ll.control_register_2().read().unwrap();
+        mock(&mut drv).clear_sent();
+        drv
+    }
+
+    /// Delay that returns at once and adds up the time asked for
+    #[derive(Default)]
+    struct Elapsed {
+        ns: u64,
+    }
+
+    impl Elapsed {
+        fn ms(&self) -> u64 {
+            self.ns / 1_000_000
+        }
+    }
+
+    impl embedded_hal::delay::DelayNs for Elapsed {
+        fn delay_ns(&mut self, ns: u32) {
+            self.ns += u64::from(ns);
+        }
+    }
+
+    #[test]
+    fn setters_preserve_other_fields() {
+        let cases: [(Setter, DrvConfig); 12] = [
+            (
+                |drv| drv.set_gate_current(GateCurrent::Low),
+                DrvConfig {
+                    gate_current: GateCurrent::Low,
+                    ..BASE
+                },
+            ),
+            (
+                |drv| drv.set_pwm_mode(false),
+                DrvConfig {
+                    pwm_mode: PwmMode::SixPwm,
+                    ..BASE
+                },
+            ),
+            (
+                |drv| drv.set_ocp_mode(OcpMode::ReportOnly),
+                DrvConfig {
+                    ocp_mode: OcpMode::ReportOnly,
+                    ..BASE
+                },
+            ),
+            (
+                |drv| drv.set_oc_threshold(OcAdjSet::Vds097mV),
+                DrvConfig {
+                    oc_adj_set: OcAdjSet::Vds097mV,
+                    ..BASE
+                },
+            ),
+            (|drv| drv.reset_gate_faults(), BASE),
+            (
+                |drv| drv.set_octw_mode(OctwMode::OcOnly),
+                DrvConfig {
+                    octw_mode: OctwMode::OcOnly,
+                    ..BASE
+                },
+            ),
+            (
+                |drv| drv.set_shunt_amplifier_gain(ShuntAmplifierGain::Gain80),
+                DrvConfig {
+                    gain: ShuntAmplifierGain::Gain80,
+                    ..BASE
+                },
+            ),
+            (
+                |drv| drv.set_dc_cal_ch1(false),
+                DrvConfig {
+                    dc_cal_ch1: false,
+                    ..BASE
+                },
+            ),
+            (
+                |drv| drv.set_dc_cal_ch2(false),
+                DrvConfig {
+                    dc_cal_ch2: false,
+                    ..BASE
+                },
+            ),
+            (
+                |drv| drv.set_dc_cal(CsaChannel::Ch1, false),
+                DrvConfig {
+                    dc_cal_ch1: false,
+                    ..BASE
+                },
+            ),
+            (
+                |drv| drv.set_dc_cal(CsaChannel::Ch2, false),
+                DrvConfig {
+                    dc_cal_ch2: false,
+                    ..BASE
+                },
+            ),
+            (
+                |drv| drv.set_oc_toff(false),
+                DrvConfig {
+                    oc_toff: false,
+                    ..BASE
+                },
+            ),
+        ];
+
+        for (set, expected) in cases {
+            let mut drv = Driver::new(MockSpi::new());
+            drv.apply_config(&BASE).unwrap();
+            set(&mut drv).unwrap();
+            assert_eq!(drv.get_config().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn async_setters_preserve_other_fields() {
+        let mut drv = AsyncDriver::new(MockSpi::new());
+        block_on(async {
+            drv.apply_config(&BASE).await.unwrap();
+
+            drv.set_ocp_mode(OcpMode::ReportOnly).await.unwrap();
+            let expected = DrvConfig {
+                ocp_mode: OcpMode::ReportOnly,
+                ..BASE
+            };
+            assert_eq!(drv.get_config().await.unwrap(), expected);
+
+            drv.set_shunt_amplifier_gain(ShuntAmplifierGain::Gain80)
+                .await
+                .unwrap();
+            let expected = DrvConfig {
+                gain: ShuntAmplifierGain::Gain80,
+                ..expected
+            };
+            assert_eq!(drv.get_config().await.unwrap(), expected);
+        });
+    }
+
+    #[test]
+    fn detached_bus_fails_until_reattached() {
+        let mut drv = Driver::new(MockSpi::new());
+        drv.set_gate_current(GateCurrent::Medium).unwrap();
+
+        let spi = drv.take_spi().expect("bus attached");
+        assert!(drv.take_spi().is_none());
+        let read = drv.get_config();
+        assert!(
+            matches!(read, Err(DrvError::NotSupported("SPI bus detached"))),
+            "{read:?}"
+        );
+        let write = drv.set_gate_current(GateCurrent::Low);
+        assert!(
+            matches!(write, Err(DrvError::NotSupported("SPI bus detached"))),
+            "{write:?}"
+        );
+
+        drv.put_spi(spi);
+        assert_eq!(drv.get_config().unwrap().gate_current, GateCurrent::Medium);
+        drv.set_gate_current(GateCurrent::Low).unwrap();
+        assert_eq!(drv.get_config().unwrap().gate_current, GateCurrent::Low);
+    }
+
+    // The words on the bus are exactly the public command encodings, MSB first
+    #[cfg(not(all(feature = "startup-dump", any(feature = "defmt", feature = "log"))))]
+    #[test]
+    fn driver_sends_public_command_words() {
+        assert_eq!(read_command(0x03).to_be_bytes(), [0x98, 0x00]);
+        assert_eq!(write_command(0x02, 0x002).to_be_bytes(), [0x10, 0x02]);
+
+        let mut drv = Driver::new(MockSpi::new());
+        drv.ll.control_register_2().read().unwrap();
+        // Read-modify-write of control register 1: N+1 read, then the write
+        drv.set_gate_current(GateCurrent::Low).unwrap();
+        assert_eq!(
+            mock(&mut drv).sent(),
+            [
+                read_command(0x03),
+                read_command(0x03),
+                read_command(0x02),
+                read_command(0x02),
+                write_command(0x02, GateCurrent::Low as u16),
+                read_command(0x02),
+            ]
+        );
+    }
+
+    // Status register 1 for a write arrives in the frame after it, not alongside it
+    #[test]
+    fn last_write_status_is_status_register_1() {
+        const OC_FAULT: u16 = 0x0401;
+        let mut drv = Driver::new(MockSpi::with_registers([OC_FAULT, 0x001, 0x000, 0x000]));
+        assert_eq!(drv.last_write_status(), None);
+
+        // The write frame itself returns the response to the preceding read of 0x02
+        drv.set_gate_current(GateCurrent::Low).unwrap();
+        assert_eq!(drv.last_write_status(), Some(OC_FAULT));
+
+        let mut drv =
+            Driver::new_verified(MockSpi::with_registers([OC_FAULT, 0x001, 0x000, 0x000]));
+        drv.set_gate_current(GateCurrent::Low).unwrap();
+        assert_eq!(drv.last_write_status(), Some(OC_FAULT));
+    }
+
+    #[test]
+    fn verified_write_to_a_matching_device_passes() {
+        let mut drv = Driver::new_verified(MockSpi::new());
+        drv.set_gate_current(GateCurrent::Low).unwrap();
+        // GATE_RESET self-clears and is left out of the comparison
+        drv.reset_gate_faults().unwrap();
+        assert_eq!(mock(&mut drv).registers()[0x02], GateCurrent::Low as u16);
+    }
+
+    #[test]
+    fn verified_write_that_does_not_stick_fails() {
+        let mut spi = MockSpi::with_registers([0x000, 0x001, 0x000, 0x008]);
+        spi.set_ignore_writes(true);
+        let mut drv = Driver::new_verified(spi);
+        let result = drv.set_oc_toff(true);
+        assert!(
+            matches!(
+                result,
+                Err(DrvError::VerifyMismatch {
+                    address: 0x03,
+                    written: 0x048,
+                    read: 0x008
+                })
+            ),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn async_verified_writes() {
+        let mut drv = AsyncDriver::new_verified(MockSpi::new());
+        block_on(drv.set_gate_current(GateCurrent::Low)).unwrap();
+        assert_eq!(
+            async_mock(&mut drv).registers()[0x02],
+            GateCurrent::Low as u16
+        );
+
+        async_mock(&mut drv).set_ignore_writes(true);
+        let result = block_on(drv.set_gate_current(GateCurrent::Medium));
+        assert!(
+            matches!(
+                result,
+                Err(DrvError::VerifyMismatch {
+                    address: 0x02,
+                    written: 0x001,
+                    read: 0x002
+                })
+            ),
+            "{result:?}"
+        );
+    }
+
+    // Exactly one write per control register, with GATE_RESET deasserted
+    #[test]
+    fn reset_controls_writes_both_control_registers_once() {
+        let mut drv = Driver::new(MockSpi::new());
+        drv.apply_config(&BASE).unwrap();
+        mock(&mut drv).clear_sent();
+
+        drv.reset_controls().unwrap();
+        let mock = mock(&mut drv);
+        // Each write is followed by the frame that clocks out status register 1
+        assert_eq!(
+            mock.sent(),
+            [
+                write_command(0x02, 0x000),
+                read_command(0x02),
+                write_command(0x03, 0x000),
+                read_command(0x03),
+            ]
+        );
+        assert_eq!(mock.registers(), MockSpi::new().registers());
+    }
+
+    // Two fields of control register 1 and one of control register 2: one write each
+    #[test]
+    fn apply_fields_writes_each_register_once() {
+        let mut drv = Driver::new(MockSpi::new());
+        drv.apply_fields(&[
+            (FieldId::OcpMode, 0b10),
+            (FieldId::GateCurrent, 0b01),
+            (FieldId::Gain, 0b11),
+        ])
+        .unwrap();
+
+        let control_1 =
+            0b10 << FieldId::OcpMode.bit_offset() | 0b01 << FieldId::GateCurrent.bit_offset();
+        let control_2 = 0b11 << FieldId::Gain.bit_offset();
+        assert_eq!(
+            writes(mock(&mut drv)),
+            [
+                write_command(0x02, control_1),
+                write_command(0x03, control_2)
+            ]
+        );
+    }
+
+    // Reads go through in dry-run mode, writes never reach the bus
+    #[test]
+    fn dry_run_sends_no_writes() {
+        let mut drv = Driver::new(MockSpi::new());
+        drv.set_dry_run(true);
+        drv.apply_config(&BASE).unwrap();
+        drv.set_gate_current(GateCurrent::Low).unwrap();
+
+        assert_eq!(writes(mock(&mut drv)), []);
+        assert_eq!(mock(&mut drv).registers(), MockSpi::new().registers());
+        assert_eq!(drv.get_config().unwrap(), DrvConfig::RESET);
+
+        drv.set_dry_run(false);
+        drv.apply_config(&BASE).unwrap();
+        assert_eq!(drv.get_config().unwrap(), BASE);
+    }
+
+    // The dump follows the first transaction only and never reads status register 1 on its
+    // own, since that would clear the latched OC bits
+    #[cfg(all(feature = "startup-dump", any(feature = "defmt", feature = "log")))]
+    #[test]
+    fn startup_dump_runs_once_without_reading_status_1() {
+        let mut drv = Driver::new(MockSpi::new());
+        drv.ll.control_register_2().read().unwrap();
+        drv.ll.control_register_2().read().unwrap();
+
+        let mut expected = [read_command(0x03); 2].to_vec();
+        for address in 0x01..=0x03 {
+            expected.extend([read_command(address); 2]);
+        }
+        expected.extend([read_command(0x03); 2]);
+        assert_eq!(mock(&mut drv).sent(), expected);
+
+        // A first read of status register 1 supplies its value to the dump
+        let mut drv = Driver::new(MockSpi::new());
+        drv.ll.status_register_1().read().unwrap();
+        let status_1_reads = mock(&mut drv)
+            .sent()
+            .iter()
+            .filter(|&&cmd| cmd == read_command(0x00))
+            .count();
+        assert_eq!(status_1_reads, 2);
+        assert_eq!(mock(&mut drv).frames(), 8);
+    }
+
+    // Several active faults resolve to the most severe one
+    #[test]
+    fn fault_source_picks_highest_priority() {
+        const FAULT: u16 = 0x400;
+        let cases = [
+            // status register 1, status register 2, expected source
+            (0x000, 0x001, None),
+            (FAULT | 0x040, 0x001, Some(Fault::OvertempWarning)),
+            (FAULT | 0x041, 0x001, Some(Fault::PhaseCLowOvercurrent)),
+            (FAULT | 0x061, 0x001, Some(Fault::PhaseAHighOvercurrent)),
+            (FAULT | 0x161, 0x001, Some(Fault::PvddUndervoltage)),
+            (FAULT | 0x361, 0x001, Some(Fault::GvddUndervoltage)),
+            (FAULT | 0x3E1, 0x001, Some(Fault::OvertempShutdown)),
+            (FAULT | 0x3E1, 0x081, Some(Fault::GvddOvervoltage)),
+            (FAULT, 0x081, Some(Fault::GvddOvervoltage)),
+        ];
+        for (status_1, status_2, expected) in cases {
+            let mut drv = Driver::new(MockSpi::with_registers([status_1, status_2, 0x000, 0x000]));
+            assert_eq!(
+                drv.fault_source().unwrap(),
+                expected,
+                "{status_1:#05x} {status_2:#05x}"
+            );
+        }
+    }
+
+    // The limit follows from the threshold programmed into the device: VDS / RDS(on)
+    #[test]
+    fn oc_limit_amps_for_known_thresholds() {
+        let cases = [
+            (OcAdjSet::Vds060mV, 2.0, 30.0),
+            (OcAdjSet::Vds250mV, 5.0, 50.0),
+            (OcAdjSet::Vds1043mV, 10.0, 104.3),
+            (OcAdjSet::Vds2400mV, 4.0, 600.0),
+        ];
+        let mut drv = Driver::new(MockSpi::new());
+        for (threshold, rds_on_mohm, amps) in cases {
+            drv.set_oc_threshold(threshold).unwrap();
+            let limit = drv.oc_limit_amps(rds_on_mohm).unwrap();
+            assert!((limit - amps).abs() < 1e-3, "{threshold:?}: {limit}");
+        }
+    }
+
+    // Thresholds up to the ceiling go through; one above it fails before reaching the bus
+    #[test]
+    fn oc_threshold_ceiling() {
+        let mut drv = Driver::new(MockSpi::new());
+        drv.set_max_oc_threshold(OcAdjSet::Vds250mV);
+
+        drv.set_oc_threshold(OcAdjSet::Vds197mV).unwrap();
+        drv.set_oc_threshold(OcAdjSet::Vds250mV).unwrap();
+        assert_eq!(
+            writes(mock(&mut drv)),
+            [
+                write_command(
+                    0x02,
+                    (OcAdjSet::Vds197mV as u16) << FieldId::OcAdjSet.bit_offset()
+                ),
+                write_command(
+                    0x02,
+                    (OcAdjSet::Vds250mV as u16) << FieldId::OcAdjSet.bit_offset()
+                ),
+            ]
+        );
+
+        mock(&mut drv).clear_sent();
+        let r = drv.set_oc_threshold(OcAdjSet::Vds282mV);
+        assert!(matches!(r, Err(DrvError::NotSupported(_))), "{r:?}");
+        assert_eq!(writes(mock(&mut drv)), []);
+        assert_eq!(drv.get_config().unwrap().oc_adj_set, OcAdjSet::Vds250mV);
+
+        drv.clear_max_oc_threshold();
+        drv.set_oc_threshold(OcAdjSet::Vds2400mV).unwrap();
+        assert_eq!(drv.get_config().unwrap().oc_adj_set, OcAdjSet::Vds2400mV);
+    }
+
+    // Every register the manifest declares reaches the device at its datasheet address, and
+    // the command words carry that address in A[14:11]
+    #[test]
+    fn manifest_addresses_match_command_encoding() {
+        for address in 0x00..=0x03u8 {
+            assert_eq!(read_command(address) >> 11, 0x10 | u16::from(address));
+            assert_eq!(write_command(address, 0x7FF) >> 11, u16::from(address));
+        }
+
+        let registers = [0x401, 0x002, 0x123, 0x045];
+        let mut drv = Driver::new(MockSpi::with_registers(registers));
+        // Get the first transaction, and with it any startup dump, out of the way
+        drv.get_device_id().unwrap();
+        mock(&mut drv).clear_sent();
+        let values = [
+            u16::from_be_bytes(drv.ll.status_register_1().read().unwrap().into()),
+            u16::from_be_bytes(drv.ll.status_register_2().read().unwrap().into()),
+            u16::from_be_bytes(drv.ll.control_register_1().read().unwrap().into()),
+            u16::from_be_bytes(drv.ll.control_register_2().read().unwrap().into()),
+        ];
+        assert_eq!(values, registers);
+        let mut expected = Vec::new();
+        for address in 0x00..=0x03 {
+            expected.extend([read_command(address); 2]);
+        }
+        assert_eq!(mock(&mut drv).sent(), expected);
+
+        mock(&mut drv).clear_sent();
+        drv.ll
+            .control_register_1()
+            .write(|r| r.set_gate_current(GateCurrent::Low))
+            .unwrap();
+        drv.ll
+            .control_register_2()
+            .write(|r| r.set_gain(ShuntAmplifierGain::Gain80))
+            .unwrap();
+        assert_eq!(
+            writes(mock(&mut drv)),
+            [
+                write_command(0x02, GateCurrent::Low as u16),
+                write_command(
+                    0x03,
+                    (ShuntAmplifierGain::Gain80 as u16) << FieldId::Gain.bit_offset()
+                ),
+            ]
+        );
+    }
+
+    // A response with the frame error bit set panics in debug builds with the feature on,
+    // naming the command and the response
+    #[cfg(all(feature = "panic-on-frame-error", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "DRV8301 frame error: command 0x8800, response 0x8001")]
+    fn frame_error_panics() {
+        // The mock answers reads with the seeded word as is, F bit included
+        let mut drv = Driver::new(MockSpi::with_registers([0x000, 0x8001, 0x000, 0x000]));
+        let _ = drv.get_device_id();
+    }
+
+    // Applying the configuration the device already holds reads it back and writes nothing
+    #[test]
+    fn repeated_apply_config_sends_no_writes() {
+        let mut drv = Driver::new(MockSpi::new());
+        drv.apply_config(&BASE).unwrap();
+        let registers = mock(&mut drv).registers();
+        mock(&mut drv).clear_sent();
+
+        drv.apply_config(&BASE).unwrap();
+        let mock = mock(&mut drv);
+        assert_eq!(
+            mock.sent(),
+            [
+                read_command(0x02),
+                read_command(0x02),
+                read_command(0x03),
+                read_command(0x03)
+            ]
+        );
+        assert_eq!(mock.registers(), registers);
+    }
+
+    // The batched snapshot decodes to the same values as the individual getters
+    #[test]
+    fn read_all_matches_individual_getters() {
+        // FAULT, OTW and FETHB_OC in status register 1, GVDD_OV in status register 2
+        let mut drv = Driver::new(MockSpi::with_registers([0x448, 0x081, 0x000, 0x000]));
+        drv.apply_config(&BASE).unwrap();
+
+        let state = drv.read_all().unwrap();
+        assert_eq!(state.faults, drv.get_fault_status().unwrap());
+        assert_eq!(state.config, drv.get_config().unwrap());
+        assert_eq!(state.config, BASE);
+        assert!(state.faults.gvdd_ov && state.faults.otw && state.faults.fethb_oc);
+    }
+
+    // Pipelining takes 5 transactions for the same snapshot read_all gets in 8
+    #[test]
+    fn read_all_pipelined_saves_transactions() {
+        let mut drv = Driver::new(MockSpi::with_registers([0x448, 0x081, 0x000, 0x000]));
+        drv.apply_config(&BASE).unwrap();
+        mock(&mut drv).clear_sent();
+
+        let state = drv.read_all().unwrap();
+        assert_eq!(mock(&mut drv).transactions(), 8);
+        mock(&mut drv).clear_sent();
+
+        assert_eq!(drv.read_all_pipelined().unwrap(), state);
+        let mock = mock(&mut drv);
+        assert_eq!(mock.transactions(), 5);
+        assert_eq!(
+            mock.sent(),
+            [0x00, 0x01, 0x02, 0x03, 0x03].map(read_command)
+        );
+    }
+
+    // An overtemperature shutdown that is gone after the reset pulse
+    #[test]
+    fn scripted_fault_then_clear() {
+        let mut drv = started(MockSpi::new());
+        mock(&mut drv).set_status_sequence(&[0x480, 0x000]);
+
+        let outcome = drv.handle_fault(&mut Elapsed::default()).unwrap();
+        assert_eq!(
+            outcome,
+            FaultOutcome {
+                had_fault: true,
+                cleared: true
+            }
+        );
+        assert_eq!(writes(mock(&mut drv)), [write_command(0x02, 0x004)]);
+        assert_eq!(drv.get_fault_status().unwrap(), FaultStatus::default());
+    }
+
+    // Status register 2 with its unused bits set until the device is ready
+    #[test]
+    fn wait_ready_polls_until_probe_passes() {
+        let mut drv = started(MockSpi::new());
+        mock(&mut drv).set_status_2_sequence(&[0x770, 0x770, 0x001]);
+        let mut delay = Elapsed::default();
+        drv.wait_ready(&mut delay, 10).unwrap();
+        assert_eq!(delay.ms(), 2);
+
+        let mut drv = started(MockSpi::new());
+        mock(&mut drv).set_status_2_sequence(&[0x770]);
+        let mut delay = Elapsed::default();
+        let r = drv.wait_ready(&mut delay, 3);
+        assert!(matches!(r, Err(DrvError::FrameError)), "{r:?}");
+        assert_eq!(delay.ms(), 3);
+    }
+
+    // No fault: no reset. A fault that clears and one that persists: one reset pulse each
+    #[test]
+    fn handle_fault_outcomes() {
+        let cases = [
+            (&[0x000][..], false, true),
+            (&[0x420, 0x000][..], true, true),
+            (&[0x420, 0x420][..], true, false),
+        ];
+        for (sequence, had_fault, cleared) in cases {
+            let mut drv = started(MockSpi::new());
+            mock(&mut drv).set_status_sequence(sequence);
+            let mut delay = Elapsed::default();
+            let outcome = drv.handle_fault(&mut delay).unwrap();
+            assert_eq!(
+                outcome,
+                FaultOutcome { had_fault, cleared },
+                "{sequence:x?}"
+            );
+
+            let resets = if had_fault {
+                &[write_command(0x02, 0x004)][..]
+            } else {
+                &[]
+            };
+            assert_eq!(writes(mock(&mut drv)), resets, "{sequence:x?}");
+            assert_eq!(delay.ms(), u64::from(had_fault), "{sequence:x?}");
+        }
+    }
+
+    // The second read may lose the clear-on-read bits of status register 1, nothing else
+    #[test]
+    fn double_read_validation() {
+        // FAULT, OTW, FETHA_OC and FETLC_OC, then only OTW once the OC latches cleared
+        let mut drv = started(MockSpi::new());
+        drv.set_double_read_validation(true);
+        mock(&mut drv).set_status_sequence(&[0x461, 0x040]);
+        let status = drv.get_fault_status().unwrap();
+        assert!(status.fault && status.otw && status.fetha_oc && status.fetlc_oc);
+
+        // OTW disappearing between the reads is corruption, not a cleared latch
+        let mut drv = started(MockSpi::new());
+        drv.set_double_read_validation(true);
+        mock(&mut drv).set_status_sequence(&[0x440, 0x400]);
+        let r = drv.get_fault_status();
+        assert!(matches!(r, Err(DrvError::FrameError)), "{r:?}");
+
+        let mut drv = started(MockSpi::new());
+        drv.set_double_read_validation(true);
+        mock(&mut drv).set_status_2_sequence(&[0x001, 0x081]);
+        let r = drv.get_fault_status();
+        assert!(matches!(r, Err(DrvError::FrameError)), "{r:?}");
+    }
+}
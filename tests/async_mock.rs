@@ -0,0 +1,35 @@
+//! The async driver end to end on the host
+//!
+//! `Drv8301Async` only needs an async `SpiDevice`, so a trivial executor and
+//! [`MockSpi`] are enough to run it without hardware or an embedded executor.
+
+use drv8301_dd::test_util::MockSpi;
+use drv8301_dd::{Drv8301Async, DrvConfig, GateCurrent, OcAdjSet, OcpMode, ShuntAmplifierGain};
+use futures::executor::block_on;
+
+#[test]
+fn bring_up_and_configure() {
+    block_on(async {
+        let mut drv = Drv8301Async::new(MockSpi::new());
+
+        drv.probe().await.unwrap();
+        assert_eq!(drv.get_device_id().await.unwrap(), 1);
+        assert!(!drv.has_fault().await.unwrap());
+
+        let config = DrvConfig {
+            gate_current: GateCurrent::Medium,
+            ocp_mode: OcpMode::OcLatchShutdown,
+            oc_adj_set: OcAdjSet::Vds250mV,
+            gain: ShuntAmplifierGain::Gain40,
+            ..DrvConfig::RESET
+        };
+        drv.apply_config(&config).await.unwrap();
+        assert_eq!(drv.get_config().await.unwrap(), config);
+
+        drv.set_oc_threshold(OcAdjSet::Vds403mV).await.unwrap();
+        let state = drv.read_all().await.unwrap();
+        assert_eq!(state.config.oc_adj_set, OcAdjSet::Vds403mV);
+        assert_eq!(state.config.gain, ShuntAmplifierGain::Gain40);
+        assert_eq!(state.faults.ok_or_fault(), Ok(()));
+    });
+}
@@ -0,0 +1,65 @@
+//! Driving the DRV8301 driver against `embedded-hal-mock` SPI expectations
+//!
+//! Every register access is a pair of 16-bit frames (N+1 protocol): a read command is sent
+//! twice and the data arrives in the second response, while a write is followed by a read
+//! command whose response carries status register 1.
+//! Each frame is its own `SpiDevice` transaction, so nSCS is released between them.
+//!
+//! The expectations are exact, so the features that add frames (`startup-dump`) or panic on
+//! a frame error (`panic-on-frame-error`) switch these tests off.
+#![cfg(not(any(
+    all(feature = "startup-dump", any(feature = "defmt", feature = "log")),
+    feature = "panic-on-frame-error"
+)))]
+
+use drv8301_dd::{Drv8301, OcAdjSet};
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+/// One 16-bit frame: `sent` on MOSI while `received` comes back on MISO
+fn frame(sent: [u8; 2], received: [u8; 2]) -> [Transaction<u8>; 3] {
+    [
+        Transaction::transaction_start(),
+        Transaction::transfer(sent.to_vec(), received.to_vec()),
+        Transaction::transaction_end(),
+    ]
+}
+
+#[test]
+fn set_oc_threshold_frames() {
+    let expectations: Vec<Transaction<u8>> = [
+        // Read control register 1: R/W=1, A=0x2 -> 0x9000. The first response belongs to
+        // whatever was sent before, the second carries the register (GATE_CURRENT 0.7 A)
+        frame([0x90, 0x00], [0x00, 0x00]),
+        frame([0x90, 0x00], [0x10, 0x01]),
+        // Write it back with OC_ADJ_SET = 12 (0.250 V) in D10:D6: 0x1000 | 12 << 6 | 0x001
+        frame([0x13, 0x01], [0x10, 0x01]),
+        // Status register 1 answers the write in the next frame, clocked with a read of the
+        // written register
+        frame([0x90, 0x00], [0x00, 0x00]),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let mut spi = Mock::new(&expectations);
+    let mut drv = Drv8301::new(spi.clone());
+    drv.set_oc_threshold(OcAdjSet::Vds250mV).unwrap();
+    spi.done();
+}
+
+#[test]
+fn frame_error_bit_fails_the_read() {
+    let expectations: Vec<Transaction<u8>> = [
+        frame([0x80, 0x00], [0x00, 0x00]),
+        // F=1 in the data frame
+        frame([0x80, 0x00], [0x80, 0x00]),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let mut spi = Mock::new(&expectations);
+    let mut drv = Drv8301::new(spi.clone());
+    assert!(drv.get_fault_status().is_err());
+    spi.done();
+}